@@ -0,0 +1,18 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use gridlife::Grid;
+
+fn new_random(c: &mut Criterion) {
+    let mut group = c.benchmark_group("new_random");
+    for size in [100, 1000] {
+        group.bench_with_input(BenchmarkId::new("unseeded", size), &size, |b, &size| {
+            b.iter(|| Grid::new_random(size, size).population);
+        });
+        group.bench_with_input(BenchmarkId::new("seeded", size), &size, |b, &size| {
+            b.iter(|| Grid::new_random_seeded(size, size, 42).population);
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, new_random);
+criterion_main!(benches);
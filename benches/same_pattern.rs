@@ -0,0 +1,27 @@
+use criterion::{criterion_group, criterion_main, Criterion};
+use gridlife::{Grid, Point};
+
+fn same_pattern(c: &mut Criterion) {
+    let mut group = c.benchmark_group("same_pattern");
+    let grid = Grid::new_random(512, 512);
+    // Near misses differ by a single cell in the last row, so comparing cells has to scan
+    // almost all of them
+    let near_misses: Vec<_> = (0..16)
+        .map(|x| {
+            let mut other = grid.clone();
+            let point = Point::new(511 - x, 511);
+            let alive = other.get(point).map_or(false, |c| c.is_alive());
+            other.set(point, !alive);
+            other
+        })
+        .collect();
+    group.bench_function("near_misses", |b| {
+        b.iter(|| near_misses.iter().filter(|g| grid.same_pattern(g)).count());
+    });
+    let identical = grid.clone();
+    group.bench_function("identical", |b| b.iter(|| grid.same_pattern(&identical)));
+    group.finish();
+}
+
+criterion_group!(benches, same_pattern);
+criterion_main!(benches);
@@ -0,0 +1,58 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use gridlife::{Grid, SparseGrid, Topology};
+
+/// Grids are generated from a fixed seed so every run measures the same patterns
+const SEED: u64 = 42;
+
+fn update_states(c: &mut Criterion) {
+    let mut group = c.benchmark_group("update_states");
+    for size in [32, 100, 128, 512, 1000] {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            let mut grid = Grid::new_random_seeded(size, size, SEED);
+            b.iter(|| grid.update_states().len());
+        });
+    }
+    group.finish();
+}
+
+fn update_states_toroidal(c: &mut Criterion) {
+    // Wrapping edges are the most expensive to look up without the precomputed table
+    let mut group = c.benchmark_group("update_states_toroidal");
+    for size in [32, 128, 512] {
+        group.bench_with_input(BenchmarkId::from_parameter(size), &size, |b, &size| {
+            let mut grid = Grid::new_random_seeded(size, size, SEED);
+            grid.topology = Topology::Toroidal;
+            b.iter(|| grid.update_states().len());
+        });
+    }
+    group.finish();
+}
+
+fn update_states_sparse(c: &mut Criterion) {
+    // A few percent of live cells, compared between the dense grid and `SparseGrid`
+    let mut group = c.benchmark_group("update_states_sparse");
+    for size in [100, 1000] {
+        let mut grid = Grid::new_empty(size, size);
+        grid.randomize_seeded(0.02, SEED);
+        group.bench_with_input(BenchmarkId::new("grid", size), &grid, |b, grid| {
+            let mut grid = grid.clone();
+            b.iter(|| grid.update_states().len());
+        });
+        group.bench_with_input(BenchmarkId::new("sparse_grid", size), &grid, |b, grid| {
+            let mut sparse = SparseGrid::from_grid(grid, (0, 0));
+            b.iter(|| {
+                sparse.update_states();
+                sparse.population()
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    update_states,
+    update_states_toroidal,
+    update_states_sparse
+);
+criterion_main!(benches);
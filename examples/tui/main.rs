@@ -1,17 +1,36 @@
-use std::{io, time::Duration};
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    process,
+    str::FromStr,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
 
 use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind};
 use ratatui::{
     buffer::Buffer,
     layout::Rect,
-    style::Stylize,
+    style::{Color, Style, Stylize},
     symbols::border,
-    text::{Line, Text},
-    widgets::{Block, Paragraph, Widget},
+    text::Line,
+    widgets::{Block, Widget},
     DefaultTerminal, Frame,
 };
 
-use gridlife::{CellState, Grid};
+use gridlife::{CellState, CycleDetector, Grid, Ruleset, Topology};
+
+/// Number of generations kept so the simulation can be stepped backwards
+const HISTORY_LIMIT: usize = 100;
+/// Shortest time between redraws, capping the screen at 30 frames per second
+const FRAME_INTERVAL: Duration = Duration::from_millis(1000 / 30);
+/// Time between generations unless `--interval` is given, matching the poll timeout the TUI
+/// used before stepping was timed separately
+const DEFAULT_STEP_INTERVAL: Duration = Duration::from_millis(10);
+/// Number of generations after which a running simulation forgets the states it has seen, so
+/// only oscillators with a shorter period are noticed
+const CYCLE_WINDOW: u32 = 1000;
+/// Furthest the view can be zoomed out, where each character shows 16 by 16 cells
+const MAX_ZOOM: u32 = 4;
 
 #[derive(Debug, Default)]
 pub struct App {
@@ -22,11 +41,49 @@ pub struct App {
     population: usize,
     height: usize,
     width: usize,
+    status: Option<String>,
+    /// Column and row of the cell edited with the keyboard while paused
+    cursor: (usize, usize),
+    /// Whether to draw the cells beyond each edge around the grid
+    ghosts: bool,
+    /// Column and row of the cell shown in the top left corner
+    offset: (usize, usize),
+    /// Number of times the view has been zoomed out, each showing twice as many cells across
+    zoom: u32,
+    /// Number of cells that fit across and down the screen at the current zoom
+    view: (usize, usize),
+    /// Time between generations while the simulation runs
+    step_interval: Duration,
+    /// States seen while running, to notice when the grid settles down
+    detector: CycleDetector,
+}
+
+/// How a running simulation came to a stop
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Outcome {
+    /// Every cell died
+    Extinct,
+    /// The grid stopped changing
+    Stable,
+    /// The grid repeats with the given period
+    Oscillating(usize),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Outcome::Extinct => write!(f, "Extinct"),
+            Outcome::Stable => write!(f, "Stable"),
+            Outcome::Oscillating(period) => write!(f, "Oscillating with period {period}"),
+        }
+    }
 }
 
 impl App {
     pub fn new(height: usize, width: usize) -> Self {
-        let grid = Grid::new_empty(width, height);
+        let (alive_glyph, dead_glyph) = detect_glyphs();
+        let mut grid = Grid::new_empty_custom_glyphs(width, height, alive_glyph, dead_glyph);
+        grid.history_limit = HISTORY_LIMIT;
         App {
             grid,
             exit: false,
@@ -35,27 +92,58 @@ impl App {
             population: 0,
             height,
             width,
+            status: None,
+            cursor: (0, 0),
+            ghosts: false,
+            offset: (0, 0),
+            zoom: 0,
+            view: (width, height),
+            step_interval: DEFAULT_STEP_INTERVAL,
+            detector: CycleDetector::new(),
         }
     }
 
     /// runs the application's main loop until the user quits
+    ///
+    /// The screen is redrawn at most every `FRAME_INTERVAL` and a running simulation is stepped
+    /// every `step_interval`, so fast simulations skip frames rather than redrawing every step.
     pub fn run(&mut self, terminal: &mut DefaultTerminal) -> io::Result<()> {
+        let mut last_draw: Option<Instant> = None;
+        let mut last_step = Instant::now();
         while !self.exit {
-            terminal.draw(|frame| self.draw(frame))?;
-            self.handle_events()?;
-            if self.run {
-                self.cycle();
+            if last_draw.map_or(true, |at| at.elapsed() >= FRAME_INTERVAL) {
+                terminal.draw(|frame| self.draw(frame))?;
+                last_draw = Some(Instant::now());
+            }
+            // Wait for input only until the next frame or step is due
+            let until_draw = last_draw.map_or(Duration::ZERO, |at| {
+                FRAME_INTERVAL.saturating_sub(at.elapsed())
+            });
+            let timeout = if self.run {
+                until_draw.min(self.step_interval.saturating_sub(last_step.elapsed()))
+            } else {
+                until_draw
+            };
+            self.handle_events(timeout)?;
+            if self.run && last_step.elapsed() >= self.step_interval {
+                self.advance();
+                last_step = Instant::now();
             }
         }
         Ok(())
     }
 
-    fn draw(&self, frame: &mut Frame) {
-        frame.render_widget(self, frame.area());
+    fn draw(&mut self, frame: &mut Frame) {
+        // Everything but the border is available for cells
+        let area = frame.area();
+        let columns = usize::from(area.width.saturating_sub(2)) / self.grid.cell_width().max(1);
+        self.view = (columns, usize::from(area.height.saturating_sub(2)));
+        self.follow_cursor();
+        frame.render_widget(&*self, area);
     }
 
-    fn handle_events(&mut self) -> io::Result<()> {
-        if event::poll(Duration::from_millis(10))? {
+    fn handle_events(&mut self, timeout: Duration) -> io::Result<()> {
+        if event::poll(timeout)? {
             match event::read()? {
                 // it's important to check that the event is a key press event as
                 // crossterm also emits key release and repeat events on Windows.
@@ -72,8 +160,21 @@ impl App {
             KeyCode::Char('q') => self.exit(),
             KeyCode::Char('r') => self.run_simulation(),
             KeyCode::Char('s') => self.stop_simulation(),
+            KeyCode::Char(' ') => self.toggle_simulation(),
+            KeyCode::Enter => self.toggle_cell(),
+            KeyCode::Left => self.move_cursor(-1, 0),
+            KeyCode::Right => self.move_cursor(1, 0),
+            KeyCode::Up => self.move_cursor(0, -1),
+            KeyCode::Down => self.move_cursor(0, 1),
             KeyCode::Char('n') => self.cycle(),
+            KeyCode::Char('b') => self.step_back(),
+            KeyCode::Char('t') => self.toggle_wrap(),
+            KeyCode::Char('g') => self.ghosts = !self.ghosts,
+            KeyCode::Char('+') => self.set_zoom(self.zoom.saturating_sub(1)),
+            KeyCode::Char('-') => self.set_zoom((self.zoom + 1).min(MAX_ZOOM)),
             KeyCode::Char('?') => self.random_grid(),
+            KeyCode::Char('w') => self.save_grid(),
+            KeyCode::Char('l') => self.load_grid(),
             _ => {}
         }
     }
@@ -82,22 +183,226 @@ impl App {
         self.population = self.grid.population;
         self.cycles += 1;
     }
+    /// Steps the running simulation, stopping it once the grid dies out or starts repeating
+    fn advance(&mut self) {
+        if let Some(outcome) = self.step_and_classify() {
+            self.stop_simulation();
+            self.status = Some(format!("{outcome} after {} cycles", self.cycles));
+        }
+    }
+    /// Steps the grid, returning how it ended up if it is no longer changing in new ways
+    fn step_and_classify(&mut self) -> Option<Outcome> {
+        self.cycle();
+        if self.grid.is_empty() {
+            return Some(Outcome::Extinct);
+        }
+        let outcome = match self.detector.observe(&self.grid) {
+            Some(1) => Some(Outcome::Stable),
+            Some(period) => Some(Outcome::Oscillating(period)),
+            None => None,
+        };
+        // Every state is remembered, so start afresh now and then to bound the memory used
+        if self.cycles % CYCLE_WINDOW == 0 {
+            self.reset_detector();
+        }
+        outcome
+    }
+    /// Forgets the states seen so far, starting again from the current grid
+    fn reset_detector(&mut self) {
+        self.detector.clear();
+        self.detector.observe(&self.grid);
+    }
+    fn step_back(&mut self) {
+        if self.grid.step_back() {
+            self.population = self.grid.population;
+            self.cycles = self.cycles.saturating_sub(1);
+            self.reset_detector();
+        }
+    }
+    /// Switches between a bounded grid and one that wraps around at the edges
+    fn toggle_wrap(&mut self) {
+        self.grid.topology = if self.grid.topology == Topology::Toroidal {
+            Topology::Bounded
+        } else {
+            Topology::Toroidal
+        };
+        self.reset_detector();
+    }
+    fn population_label(&self) -> String {
+        if self.grid.is_empty() {
+            "empty".to_string()
+        } else if self.grid.is_full() {
+            "full".to_string()
+        } else {
+            self.population.to_string()
+        }
+    }
     fn exit(&mut self) {
         self.exit = true;
     }
     fn run_simulation(&mut self) {
         self.run = true;
+        self.reset_detector();
     }
     fn stop_simulation(&mut self) {
         self.run = false;
     }
+    fn toggle_simulation(&mut self) {
+        if self.run {
+            self.stop_simulation();
+        } else {
+            self.run_simulation();
+        }
+    }
+    fn move_cursor(&mut self, dx: isize, dy: isize) {
+        let step = |pos: usize, delta: isize, len: usize| {
+            (pos as isize + delta).clamp(0, len.saturating_sub(1) as isize) as usize
+        };
+        let (x, y) = self.cursor;
+        self.cursor = (step(x, dx, self.grid.width), step(y, dy, self.grid.height));
+        self.follow_cursor();
+    }
+    fn set_zoom(&mut self, zoom: u32) {
+        self.zoom = zoom;
+        self.follow_cursor();
+    }
+    /// Number of cells across and down each character on screen samples
+    fn scale(&self) -> usize {
+        1 << self.zoom
+    }
+    /// Pans the view just far enough that the cursor is on screen
+    fn follow_cursor(&mut self) {
+        let ghost = usize::from(self.ghosts);
+        let follow = |offset: usize, cursor: usize, span: usize| {
+            let cursor = cursor + ghost;
+            if span == 0 {
+                offset
+            } else if cursor < offset {
+                cursor
+            } else if cursor >= offset + span {
+                cursor + 1 - span
+            } else {
+                offset
+            }
+        };
+        let scale = self.scale();
+        self.offset = (
+            follow(self.offset.0, self.cursor.0, self.view.0 * scale),
+            follow(self.offset.1, self.cursor.1, self.view.1 * scale),
+        );
+    }
+    /// Flips the cell under the cursor, only while the simulation is paused
+    fn toggle_cell(&mut self) {
+        if self.run {
+            return;
+        }
+        let (x, y) = self.cursor;
+        if self.grid.toggle(x, y).is_some() {
+            self.population = self.grid.population;
+        }
+    }
+    fn save_grid(&mut self) {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs())
+            .unwrap_or_default();
+        self.save_grid_to(&PathBuf::from(format!("pattern-{timestamp}.txt")));
+    }
+    fn save_grid_to(&mut self, path: &Path) {
+        self.status = Some(match fs::write(path, self.grid.to_plaintext()) {
+            Ok(()) => format!("Saved {}", path.display()),
+            Err(e) => format!("Save failed: {e}"),
+        });
+    }
+    fn load_grid(&mut self) {
+        match latest_pattern_file() {
+            Some(path) => self.load_grid_from(&path),
+            None => self.status = Some("Load failed: no pattern-*.txt file found".to_string()),
+        }
+    }
+    fn load_grid_from(&mut self, path: &Path) {
+        let grid = fs::read_to_string(path)
+            .map_err(|e| e.to_string())
+            .and_then(|text| Grid::from_plaintext(&text).map_err(|e| e.to_string()))
+            .map(|mut grid| {
+                grid.set_alive_glyph(self.grid.alive_glyph);
+                grid.set_dead_glyph(self.grid.dead_glyph);
+                grid
+            });
+        match grid {
+            Ok(grid) => {
+                self.set_grid(grid);
+                self.status = Some(format!("Loaded {}", path.display()));
+            }
+            Err(e) => self.status = Some(format!("Load failed: {e}")),
+        }
+    }
+    /// Replaces the grid, keeping the current topology and starting again from generation 0
+    fn set_grid(&mut self, mut grid: Grid<CellState>) {
+        grid.history_limit = HISTORY_LIMIT;
+        grid.topology = self.grid.topology;
+        self.width = grid.width;
+        self.height = grid.height;
+        self.population = grid.population;
+        self.grid = grid;
+        self.move_cursor(0, 0);
+        self.cycles = 0;
+        self.reset_detector();
+    }
     fn random_grid(&mut self) {
-        self.grid = Grid::new_random(self.width, self.height);
+        self.grid.randomize(0.5);
         self.grid.update_states();
         self.cycles = 0;
+        self.reset_detector();
+    }
+}
+
+/// Picks the alive and dead glyphs for the current terminal from its environment, see
+/// `detect_glyphs_from`
+fn detect_glyphs() -> (char, char) {
+    let no_unicode = env::var_os("NO_UNICODE").map_or(false, |v| !v.is_empty());
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|key| env::var(key).ok().filter(|v| !v.is_empty()));
+    detect_glyphs_from(no_unicode, locale.as_deref())
+}
+
+/// Picks the alive and dead glyphs for a terminal with the given `locale`.
+/// `no_unicode` forces ASCII glyphs, otherwise Unicode is used when the locale advertises
+/// UTF-8 support.
+fn detect_glyphs_from(no_unicode: bool, locale: Option<&str>) -> (char, char) {
+    let locale = locale.unwrap_or_default().to_lowercase();
+    if !no_unicode && (locale.contains("utf-8") || locale.contains("utf8")) {
+        ('█', ' ')
+    } else {
+        ('#', '.')
     }
 }
 
+/// Colour used to render a live cell, shifting from newly born to long lived
+fn age_color(age: u32) -> Color {
+    match age {
+        0 => Color::LightGreen,
+        1..=4 => Color::Green,
+        5..=19 => Color::Yellow,
+        _ => Color::Red,
+    }
+}
+
+/// Finds the most recently modified pattern file written by `App::save_grid`
+fn latest_pattern_file() -> Option<PathBuf> {
+    fs::read_dir(".")
+        .ok()?
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            name.starts_with("pattern-") && name.ends_with(".txt")
+        })
+        .max_by_key(|entry| entry.metadata().and_then(|m| m.modified()).ok())
+        .map(|entry| entry.path())
+}
+
 impl Widget for &App {
     fn render(self, area: Rect, buf: &mut Buffer) {
         let title = Line::from(" Game of Life ".bold());
@@ -108,32 +413,242 @@ impl Widget for &App {
             "<r>".blue().bold(),
             " Stop".into(),
             "<s>".blue().bold(),
+            " Toggle".into(),
+            "<space>".blue().bold(),
+            " Edit".into(),
+            "<enter>".blue().bold(),
+            " Move".into(),
+            "<arrows>".blue().bold(),
             " Single Cycle".into(),
             "<n>".blue().bold(),
+            " Back".into(),
+            "<b>".blue().bold(),
+            " Wrap".into(),
+            "<t>".blue().bold(),
+            " Ghosts".into(),
+            "<g>".blue().bold(),
+            " Zoom".into(),
+            "<+/->".blue().bold(),
             " Regenerate".into(),
             "<?>".blue().bold(),
+            " Save".into(),
+            "<w>".blue().bold(),
+            " Load".into(),
+            "<l>".blue().bold(),
             " Population: ".into(),
-            format!("{}", self.population).red().bold(),
+            self.population_label().red().bold(),
             " Cycles: ".into(),
-            format!("{} ", self.cycles).red().bold(),
+            format!("{}", self.cycles).red().bold(),
+            " View: ".into(),
+            format!("{},{} x{} ", self.offset.0, self.offset.1, self.scale())
+                .red()
+                .bold(),
         ]);
-        let block = Block::bordered()
+        let mut block = Block::bordered()
             .title(title.centered())
             .title_bottom(instructions.centered())
             .border_set(border::THICK);
+        if let Some(status) = &self.status {
+            block = block.title(Line::from(format!(" {status} ")).right_aligned());
+        }
 
-        let grid_out = self.grid.to_string();
-        let lines: Vec<Line> = grid_out.lines().map(Line::from).collect();
-        let grid_text = Text::from(lines);
+        // Ghost cells are drawn around the grid, so its own cells are offset by one
+        let ghost_grid;
+        let (shown, offset) = if self.ghosts {
+            ghost_grid = self.grid.with_ghost_border();
+            (&ghost_grid, 1)
+        } else {
+            (&self.grid, 0)
+        };
+        let inner = block.inner(area);
+        block.render(area, buf);
 
-        Paragraph::new(grid_text).block(block).render(area, buf);
+        let ages = self.grid.ages();
+        let cell_width = self.grid.cell_width();
+        let scale = self.scale();
+        let rows = (self.offset.1..shown.height).step_by(scale);
+        for (y, top) in rows.enumerate().take(inner.height as usize) {
+            let columns = (self.offset.0..shown.width).step_by(scale);
+            for (x, left) in columns.enumerate() {
+                let column = x * cell_width;
+                if column + cell_width > inner.width as usize {
+                    break;
+                }
+                // Zoomed out, a block of cells is shown by its first live cell if it has any
+                let block: Vec<(usize, usize)> = (top..(top + scale).min(shown.height))
+                    .flat_map(|y| (left..(left + scale).min(shown.width)).map(move |x| (x, y)))
+                    .collect();
+                let (bx, by) = block
+                    .iter()
+                    .copied()
+                    .find(|&(x, y)| !shown.cells[y * shown.width + x].is_dead())
+                    .unwrap_or((left, top));
+                let cell = &shown.cells[by * shown.width + bx];
+                // Column and row on the grid itself, or `None` for a ghost cell
+                let on_grid = bx
+                    .checked_sub(offset)
+                    .zip(by.checked_sub(offset))
+                    .filter(|&(x, y)| x < self.grid.width && y < self.grid.height);
+                let style = match (cell, on_grid) {
+                    (CellState::Alive(_), Some((x, y))) => {
+                        Style::new().fg(age_color(ages[y * self.grid.width + x]))
+                    }
+                    (CellState::Alive(_), None) => Style::new().dim(),
+                    (CellState::Dying(..), _) => Style::new().gray(),
+                    (CellState::Dead(_), _) => Style::new().dark_gray(),
+                };
+                let (cx, cy) = self.cursor;
+                let has_cursor = block.contains(&(cx + offset, cy + offset));
+                let style = if !self.run && has_cursor {
+                    style.reversed().slow_blink()
+                } else {
+                    style
+                };
+                // Pad narrow glyphs so columns line up with any wide glyphs
+                let glyph = format!("{cell}{}", " ".repeat(cell_width - cell.width()));
+                buf.set_string(inner.x + column as u16, inner.y + y as u16, glyph, style);
+            }
+        }
     }
 }
 
+const USAGE: &str =
+    "Usage: tui [--width N] [--height N] [--steps N] [--rule RULE] [--seed N] [--load PATH] \
+     [--interval MS]";
+
+/// Options given on the command line, all of which are optional
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    width: Option<usize>,
+    height: Option<usize>,
+    /// Run this many generations without the TUI and print the final grid
+    steps: Option<usize>,
+    rule: Option<Ruleset>,
+    /// Start from a random soup made with this seed
+    seed: Option<u64>,
+    /// Start from the RLE or plaintext pattern in this file
+    load: Option<PathBuf>,
+    /// Milliseconds between generations in the TUI, 10 by default and as fast as possible if 0
+    interval: Option<u64>,
+}
+
+impl Args {
+    fn parse(args: impl IntoIterator<Item = String>) -> Result<Args, String> {
+        fn value<T: FromStr>(flag: &str, value: Option<String>) -> Result<T, String>
+        where
+            T::Err: fmt::Display,
+        {
+            let value = value.ok_or_else(|| format!("{flag} needs a value"))?;
+            value
+                .parse()
+                .map_err(|e| format!("invalid value {value:?} for {flag}: {e}"))
+        }
+
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(flag) = args.next() {
+            match flag.as_str() {
+                "--width" => parsed.width = Some(value(&flag, args.next())?),
+                "--height" => parsed.height = Some(value(&flag, args.next())?),
+                "--steps" => parsed.steps = Some(value(&flag, args.next())?),
+                "--rule" => parsed.rule = Some(value(&flag, args.next())?),
+                "--seed" => parsed.seed = Some(value(&flag, args.next())?),
+                "--load" => parsed.load = Some(value(&flag, args.next())?),
+                "--interval" => parsed.interval = Some(value(&flag, args.next())?),
+                _ => return Err(format!("unexpected argument {flag:?}")),
+            }
+        }
+        Ok(parsed)
+    }
+
+    /// Makes the grid to start from, either the loaded pattern centred on a canvas of the
+    /// given `--width` and `--height`, by default just big enough for it, or a `width` by
+    /// `height` grid which is empty unless seeded
+    fn starting_grid(&self, width: usize, height: usize) -> Result<Grid<CellState>, String> {
+        let (alive_glyph, dead_glyph) = detect_glyphs();
+        let mut grid = match &self.load {
+            Some(path) => {
+                let mut pattern = load_pattern(path)?;
+                let rules = pattern.rules;
+                let width = self.width.unwrap_or(pattern.width);
+                let height = self.height.unwrap_or(pattern.height);
+                pattern.set_alive_glyph(alive_glyph);
+                pattern.set_dead_glyph(dead_glyph);
+                let mut grid = pattern.centered_on(width, height);
+                grid.rules = rules;
+                grid
+            }
+            None => {
+                let width = self.width.unwrap_or(width);
+                let height = self.height.unwrap_or(height);
+                let mut grid =
+                    Grid::new_empty_custom_glyphs(width, height, alive_glyph, dead_glyph);
+                match self.seed {
+                    Some(seed) => grid.randomize_seeded(0.5, seed),
+                    // A headless run of an empty grid would have nothing to show
+                    None if self.steps.is_some() => grid.randomize(0.5),
+                    None => {}
+                }
+                grid
+            }
+        };
+        if let Some(rule) = self.rule {
+            grid.rules = rule;
+        }
+        Ok(grid)
+    }
+}
+
+/// Reads a pattern from `path`, in RLE format if it ends with `.rle` and plaintext otherwise
+fn load_pattern(path: &Path) -> Result<Grid<CellState>, String> {
+    let text = fs::read_to_string(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let grid = if path
+        .extension()
+        .map_or(false, |ext| ext.eq_ignore_ascii_case("rle"))
+    {
+        Grid::from_rle_reader(text.as_bytes()).map_err(|e| e.to_string())
+    } else {
+        Grid::from_plaintext(&text).map_err(|e| e.to_string())
+    };
+    grid.map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Runs `steps` generations of the grid described by `args` without the TUI, returning the
+/// final grid
+fn run_headless(args: &Args, steps: usize) -> Result<Grid<CellState>, String> {
+    let mut grid = args.starting_grid(80, 24)?;
+    for _ in 0..steps {
+        grid.update_states();
+    }
+    Ok(grid)
+}
+
+/// Prints `message` and exits with `code`, for errors found before the terminal is taken over
+fn exit_with(message: &str, code: i32) -> ! {
+    eprintln!("error: {message}");
+    process::exit(code)
+}
+
 fn main() -> io::Result<()> {
+    let args =
+        Args::parse(env::args().skip(1)).unwrap_or_else(|e| exit_with(&format!("{e}\n{USAGE}"), 2));
+    if let Some(steps) = args.steps {
+        let grid = run_headless(&args, steps).unwrap_or_else(|e| exit_with(&e, 1));
+        print!("{grid}");
+        return Ok(());
+    }
+
+    let (width, height) = crossterm::terminal::size()?;
+    let grid = args
+        .starting_grid(width as usize - 1, height as usize - 1)
+        .unwrap_or_else(|e| exit_with(&e, 1));
+    let mut app = App::new(grid.height, grid.width);
+    app.set_grid(grid);
+    if let Some(interval) = args.interval {
+        app.step_interval = Duration::from_millis(interval);
+    }
     let mut terminal = ratatui::init();
-    let s = terminal.size()?;
-    let app_result = App::new(s.height as usize - 1, s.width as usize - 1).run(&mut terminal);
+    let app_result = app.run(&mut terminal);
     ratatui::restore();
     app_result
 }
@@ -141,39 +656,67 @@ fn main() -> io::Result<()> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use ratatui::style::Style;
+    use ratatui::style::Modifier;
 
     #[test]
     fn render() {
         let app = App::default();
-        let mut buf = Buffer::empty(Rect::new(0, 0, 100, 4));
+        let mut buf = Buffer::empty(Rect::new(0, 0, 200, 4));
 
         app.render(buf.area, &mut buf);
         let mut expected = Buffer::with_lines(vec![
-        "┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ Game of Life ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓",
-        "┃                                                                                                  ┃",
-        "┃                                                                                                  ┃",
-        "┗━━━━━━━━━ Quit <Q>  Run<r> Stop<s> Single Cycle<n> Regenerate<?> Population: 0 Cycles: 0 ━━━━━━━━━┛",
+        "┏━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━ Game of Life ━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━┓",
+        "┃                                                                                                                                                                                                      ┃",
+        "┃                                                                                                                                                                                                      ┃",
+        "┗━━━━━ Quit <Q>  Run<r> Stop<s> Toggle<space> Edit<enter> Move<arrows> Single Cycle<n> Back<b> Wrap<t> Ghosts<g> Zoom<+/-> Regenerate<?> Save<w> Load<l> Population: empty Cycles: 0 View: 0,0 x1 ━━━━━┛",
         ]);
         let title_style = Style::new().bold();
         let counter_style = Style::new().red().bold();
         let key_style = Style::new().blue().bold();
+        let dead_style = Style::new().dark_gray();
+        let cursor_style = dead_style.reversed().slow_blink();
         // Game of Life
-        expected.set_style(Rect::new(43, 0, 14, 1), title_style);
+        expected.set_style(Rect::new(93, 0, 14, 1), title_style);
+        // cursor
+        expected.set_style(Rect::new(1, 1, 1, 1), cursor_style);
+        // dead cells
+        expected.set_style(Rect::new(2, 1, 9, 1), dead_style);
+        // dead cells
+        expected.set_style(Rect::new(1, 2, 10, 1), dead_style);
         // <Q>
-        expected.set_style(Rect::new(16, 3, 4, 1), key_style);
+        expected.set_style(Rect::new(12, 3, 4, 1), key_style);
         // <r>
-        expected.set_style(Rect::new(24, 3, 3, 1), key_style);
-        //<s>
-        expected.set_style(Rect::new(32, 3, 3, 1), key_style);
-        //<n>
-        expected.set_style(Rect::new(48, 3, 3, 1), key_style);
-        //<?>
-        expected.set_style(Rect::new(62, 3, 3, 1), key_style);
+        expected.set_style(Rect::new(20, 3, 3, 1), key_style);
+        // <s>
+        expected.set_style(Rect::new(28, 3, 3, 1), key_style);
+        // <space>
+        expected.set_style(Rect::new(38, 3, 7, 1), key_style);
+        // <enter>
+        expected.set_style(Rect::new(50, 3, 7, 1), key_style);
+        // <arrows>
+        expected.set_style(Rect::new(62, 3, 8, 1), key_style);
+        // <n>
+        expected.set_style(Rect::new(83, 3, 3, 1), key_style);
+        // <b>
+        expected.set_style(Rect::new(91, 3, 3, 1), key_style);
+        // <t>
+        expected.set_style(Rect::new(99, 3, 3, 1), key_style);
+        // <g>
+        expected.set_style(Rect::new(109, 3, 3, 1), key_style);
+        // <+/->
+        expected.set_style(Rect::new(117, 3, 5, 1), key_style);
+        // <?>
+        expected.set_style(Rect::new(133, 3, 3, 1), key_style);
+        // <w>
+        expected.set_style(Rect::new(141, 3, 3, 1), key_style);
+        // <l>
+        expected.set_style(Rect::new(149, 3, 3, 1), key_style);
+        // empty
+        expected.set_style(Rect::new(165, 3, 5, 1), counter_style);
         // 0
-        expected.set_style(Rect::new(78, 3, 1, 1), counter_style);
-        // 0
-        expected.set_style(Rect::new(88, 3, 2, 1), counter_style);
+        expected.set_style(Rect::new(179, 3, 1, 1), counter_style);
+        // 0,0 x1
+        expected.set_style(Rect::new(187, 3, 7, 1), counter_style);
         assert_eq!(buf, expected);
     }
 
@@ -183,6 +726,13 @@ mod tests {
         app.handle_key_event(KeyCode::Char('n').into());
         assert_eq!(app.cycles, 1);
 
+        let mut app = App::new(5, 5);
+        app.handle_key_event(KeyCode::Char('n').into());
+        app.handle_key_event(KeyCode::Char('b').into());
+        assert_eq!(app.cycles, 0);
+        app.handle_key_event(KeyCode::Char('b').into());
+        assert_eq!(app.cycles, 0);
+
         let mut app = App::default();
         app.handle_key_event(KeyCode::Char('q').into());
         assert!(app.exit);
@@ -192,6 +742,10 @@ mod tests {
         assert!(app.run);
         app.handle_key_event(KeyCode::Char('s').into());
         assert!(!app.run);
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert!(app.run);
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert!(!app.run);
         let mut app = App::default();
         app.handle_key_event(KeyCode::Char('?').into());
         // assert!(app.population != 0);
@@ -199,4 +753,220 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn cursor_editing() {
+        let mut app = App::new(3, 4);
+        app.handle_key_event(KeyCode::Right.into());
+        app.handle_key_event(KeyCode::Down.into());
+        app.handle_key_event(KeyCode::Enter.into());
+        assert_eq!(app.cursor, (1, 1));
+        assert!(app.grid.cells[5].is_alive());
+        assert_eq!(app.population, 1);
+
+        // The cursor stops at the edges of the grid
+        for _ in 0..5 {
+            app.handle_key_event(KeyCode::Right.into());
+            app.handle_key_event(KeyCode::Up.into());
+        }
+        assert_eq!(app.cursor, (3, 0));
+
+        // Space runs and pauses the simulation rather than editing
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert!(app.run);
+        app.handle_key_event(KeyCode::Char(' ').into());
+        assert!(!app.run);
+        assert!(app.grid.cells[3].is_dead());
+    }
+
+    #[test]
+    fn wrap_and_ghosts() {
+        let mut app = App::new(3, 3);
+        app.grid.set((2, 1).into(), true);
+        app.handle_key_event(KeyCode::Char('t').into());
+        assert_eq!(app.grid.topology, Topology::Toroidal);
+        app.handle_key_event(KeyCode::Char('g').into());
+        assert!(app.ghosts);
+
+        // The live cell on the right edge is repeated in the ghost column on the left
+        let mut buf = Buffer::empty(Rect::new(0, 0, 8, 7));
+        app.render(buf.area, &mut buf);
+        assert_eq!(buf[(1, 3)].symbol(), app.grid.alive_glyph.to_string());
+        assert_eq!(buf[(4, 3)].symbol(), app.grid.alive_glyph.to_string());
+        assert!(buf[(1, 3)].modifier.contains(Modifier::DIM));
+
+        app.handle_key_event(KeyCode::Char('t').into());
+        assert_eq!(app.grid.topology, Topology::Bounded);
+    }
+
+    #[test]
+    fn zoom_and_pan() {
+        let mut app = App::new(10, 10);
+        app.view = (4, 3);
+        for _ in 0..6 {
+            app.handle_key_event(KeyCode::Right.into());
+        }
+        assert_eq!(app.offset, (3, 0));
+        app.handle_key_event(KeyCode::Char('-').into());
+        assert_eq!((app.scale(), app.offset), (2, (3, 0)));
+        for _ in 0..6 {
+            app.handle_key_event(KeyCode::Left.into());
+        }
+        assert_eq!(app.offset, (0, 0));
+
+        // Each character shows a two by two block, alive if any cell in it is
+        app.grid.set((3, 1).into(), true);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 4, 4));
+        app.render(buf.area, &mut buf);
+        assert_eq!(buf[(2, 1)].symbol(), app.grid.alive_glyph.to_string());
+        assert_eq!(buf[(1, 1)].symbol(), app.grid.dead_glyph.to_string());
+
+        app.handle_key_event(KeyCode::Char('+').into());
+        app.handle_key_event(KeyCode::Char('+').into());
+        assert_eq!(app.scale(), 1);
+    }
+
+    #[test]
+    fn parse_args() {
+        let args = |line: &str| Args::parse(line.split_whitespace().map(String::from));
+        assert_eq!(args(""), Ok(Args::default()));
+        assert_eq!(
+            args("--width 20 --height 10 --steps 5 --rule B36/S23 --seed 7"),
+            Ok(Args {
+                width: Some(20),
+                height: Some(10),
+                steps: Some(5),
+                rule: Some(Ruleset::new(&[3, 6], &[2, 3])),
+                seed: Some(7),
+                load: None,
+                interval: None,
+            })
+        );
+        assert_eq!(args("--interval 50").unwrap().interval, Some(50));
+        assert!(args("--width").unwrap_err().contains("needs a value"));
+        assert!(args("--steps many").unwrap_err().contains("invalid value"));
+        assert!(args("--rule B9").unwrap_err().contains("invalid value"));
+        assert!(args("--colour")
+            .unwrap_err()
+            .contains("unexpected argument"));
+    }
+
+    #[test]
+    fn headless_run_is_seeded() {
+        let args = Args::parse(["--width", "12", "--height", "8", "--seed", "3"].map(String::from))
+            .unwrap();
+        let grid = run_headless(&args, 4).unwrap();
+        assert_eq!((grid.width, grid.height), (12, 8));
+        assert!(grid.same_pattern(&run_headless(&args, 4).unwrap()));
+    }
+
+    #[test]
+    fn starting_grid_from_pattern() {
+        let path = env::temp_dir().join("gridlife-start-test.rle");
+        fs::write(&path, "x = 3, y = 1, rule = B36/S23\n3o!\n").unwrap();
+        let mut args = Args {
+            load: Some(path.clone()),
+            ..Args::default()
+        };
+        let grid = args.starting_grid(80, 24).unwrap();
+        assert_eq!((grid.width, grid.height, grid.population), (3, 1, 3));
+        assert_eq!(grid.rules, Ruleset::new(&[3, 6], &[2, 3]));
+
+        // Centred on a larger canvas
+        args.width = Some(5);
+        args.height = Some(3);
+        let grid = args.starting_grid(80, 24).unwrap();
+        assert!(grid.cells[6..9].iter().all(CellState::is_alive));
+        assert_eq!(grid.population, 3);
+
+        fs::write(&path, "x = 3, y = 1\n3q!\n").unwrap();
+        assert!(args.starting_grid(80, 24).is_err());
+        fs::remove_file(&path).unwrap();
+        args.load = Some(env::temp_dir().join("gridlife-missing.cells"));
+        assert!(args.starting_grid(80, 24).is_err());
+    }
+
+    #[test]
+    fn stops_when_settled() {
+        let mut app = App::new(5, 5);
+        app.grid.set_alive_many(&[(1, 2), (2, 2), (3, 2)]);
+        app.run_simulation();
+        app.advance();
+        assert!(app.run);
+        app.advance();
+        assert!(!app.run);
+        assert_eq!(
+            app.status.as_deref(),
+            Some("Oscillating with period 2 after 2 cycles")
+        );
+
+        app.grid.set_alive_many(&[(0, 0), (1, 0), (0, 1), (1, 1)]);
+        app.grid.set_dead_many(&[(1, 2), (2, 2), (3, 2)]);
+        app.run_simulation();
+        app.advance();
+        assert_eq!(app.status.as_deref(), Some("Stable after 3 cycles"));
+
+        app.grid.clear();
+        app.run_simulation();
+        assert_eq!(app.step_and_classify(), Some(Outcome::Extinct));
+    }
+
+    #[test]
+    fn detect_glyphs_no_unicode_override() {
+        assert_eq!(detect_glyphs_from(false, Some("en_GB.UTF-8")), ('█', ' '));
+        assert_eq!(detect_glyphs_from(true, Some("en_GB.UTF-8")), ('#', '.'));
+        assert_eq!(detect_glyphs_from(false, Some("C")), ('#', '.'));
+        assert_eq!(detect_glyphs_from(false, None), ('#', '.'));
+    }
+
+    #[test]
+    fn age_color_buckets() {
+        assert_eq!(age_color(0), Color::LightGreen);
+        assert_eq!(age_color(3), Color::Green);
+        assert_eq!(age_color(10), Color::Yellow);
+        assert_eq!(age_color(100), Color::Red);
+    }
+
+    #[test]
+    fn save_grid_to() {
+        let mut app = App::new(3, 3);
+        app.random_grid();
+        let path = env::temp_dir().join("gridlife-save-test.txt");
+        app.save_grid_to(&path);
+        assert_eq!(fs::read_to_string(&path).unwrap(), app.grid.to_plaintext());
+        assert!(app.status.as_ref().unwrap().starts_with("Saved"));
+        // Saves are plaintext whatever the glyphs, so they load back into the same grid
+        let saved = app.grid.to_string();
+        app.load_grid_from(&path);
+        assert_eq!(app.grid.to_string(), saved);
+        assert!(load_pattern(&path).is_ok());
+        fs::remove_file(&path).unwrap();
+
+        app.save_grid_to(Path::new("/nonexistent-dir/pattern.txt"));
+        assert!(app.status.as_ref().unwrap().starts_with("Save failed"));
+    }
+
+    #[test]
+    fn load_grid_from() {
+        let path = env::temp_dir().join("gridlife-load-test.txt");
+        fs::write(&path, ".O\nOOO\n...\n").unwrap();
+        let mut app = App::default();
+        app.cycle();
+        app.load_grid_from(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!((app.width, app.height), (3, 3));
+        assert_eq!(app.grid.to_string(), " X \nXXX\n   \n");
+        assert_eq!(app.population, 4);
+        assert_eq!(app.cycles, 0);
+
+        app.load_grid_from(Path::new("/nonexistent-dir/pattern.txt"));
+        assert!(app.status.as_ref().unwrap().starts_with("Load failed"));
+        assert_eq!((app.width, app.height), (3, 3));
+
+        // Files in the terminal's glyphs rather than plaintext are rejected
+        fs::write(&path, " X\nXXX\n").unwrap();
+        app.load_grid_from(&path);
+        fs::remove_file(&path).unwrap();
+        assert!(app.status.as_ref().unwrap().starts_with("Load failed"));
+    }
 }
@@ -0,0 +1,189 @@
+//! Builder for configuring a `Grid`
+
+use crate::{check_glyphs, check_range, CellState, Grid, GridError, Ruleset, Topology};
+
+#[derive(Debug, Clone)]
+/// `GridBuilder` configures and creates a `Grid`.
+/// Anything not set matches `Grid::default()`: an empty 10x10 grid using `X` for alive, ` ` for
+/// dead, the rules of Conways game of life and bounded edges.
+/// ```
+/// use gridlife::{GridBuilder, Ruleset, Topology};
+/// let grid = GridBuilder::new()
+///     .size(20, 10)
+///     .alive_glyph('#')
+///     .dead_glyph('.')
+///     .rules(Ruleset::new(&[3, 6], &[2, 3]))
+///     .topology(Topology::Toroidal)
+///     .seed(42)
+///     .build()
+///     .unwrap();
+/// assert_eq!(grid.cells.len(), 200);
+/// ```
+pub struct GridBuilder {
+    width: usize,
+    height: usize,
+    alive_glyph: char,
+    dead_glyph: char,
+    rules: Ruleset,
+    states: u8,
+    topology: Topology,
+    range: u32,
+    seed: Option<u64>,
+}
+
+impl Default for GridBuilder {
+    fn default() -> Self {
+        let grid = Grid::default();
+        GridBuilder {
+            width: grid.width,
+            height: grid.height,
+            alive_glyph: grid.alive_glyph,
+            dead_glyph: grid.dead_glyph,
+            rules: grid.rules,
+            states: grid.states,
+            topology: grid.topology,
+            range: grid.range,
+            seed: None,
+        }
+    }
+}
+
+impl GridBuilder {
+    /// Create a new `GridBuilder` with the default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the `width` and `height` of the grid
+    pub fn size(mut self, width: usize, height: usize) -> Self {
+        self.width = width;
+        self.height = height;
+        self
+    }
+
+    /// Set the glyph used to display an alive population
+    pub fn alive_glyph(mut self, glyph: char) -> Self {
+        self.alive_glyph = glyph;
+        self
+    }
+
+    /// Set the glyph used to display a dead population
+    pub fn dead_glyph(mut self, glyph: char) -> Self {
+        self.dead_glyph = glyph;
+        self
+    }
+
+    /// Set the rules used to generate each generation
+    pub fn rules(mut self, rules: Ruleset) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Set the number of states a cell can be in, see `Grid::states`
+    pub fn states(mut self, states: u8) -> Self {
+        self.states = states;
+        self
+    }
+
+    /// Set how the edges of the grid behave
+    pub fn topology(mut self, topology: Topology) -> Self {
+        self.topology = topology;
+        self
+    }
+
+    /// Set how far away cells are counted as neighbours, see `Grid::set_range`
+    pub fn range(mut self, range: u32) -> Self {
+        self.range = range;
+        self
+    }
+
+    /// Fill the grid with a random distribution of Alive/Dead cells generated from `seed`,
+    /// rather than leaving it empty. The same seed always produces the same grid.
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Create the configured `Grid`, failing if the size is unusable as for `Grid::try_new`, the
+    /// glyphs are invalid as for `Grid::new_empty_custom_glyphs` or the range is invalid as for
+    /// `Grid::set_range`
+    pub fn build(self) -> Result<Grid<CellState>, GridError> {
+        check_glyphs(self.alive_glyph, self.dead_glyph)?;
+        check_range(self.range)?;
+        let mut grid = Grid::try_new(self.width, self.height)?;
+        grid.alive_glyph = self.alive_glyph;
+        grid.dead_glyph = self.dead_glyph;
+        grid.background = CellState::Dead(self.dead_glyph);
+        grid.rules = self.rules;
+        grid.states = self.states;
+        grid.topology = self.topology;
+        grid.range = self.range;
+        match self.seed {
+            Some(seed) => grid.randomize_seeded(0.5, seed),
+            None => grid.clear(),
+        }
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder_defaults_match_default() {
+        let built = GridBuilder::new().build().unwrap();
+        let default = Grid::default();
+        assert_eq!(built.to_string(), default.to_string());
+        assert_eq!(built.rules, default.rules);
+        assert_eq!(built.topology, default.topology);
+        assert_eq!(built.states, default.states);
+        assert_eq!(built.population, 0);
+    }
+
+    #[test]
+    fn test_builder_seed_is_deterministic() {
+        let build = |seed| {
+            GridBuilder::new()
+                .size(8, 8)
+                .alive_glyph('#')
+                .dead_glyph('.')
+                .seed(seed)
+                .build()
+                .unwrap()
+        };
+        let a = build(1);
+        assert_eq!(a.to_string(), build(1).to_string());
+        assert_ne!(a.to_string(), build(2).to_string());
+        assert!(a.to_string().chars().all(|c| "#.\n".contains(c)));
+        assert_eq!(a.population, a.calculate_population());
+    }
+
+    #[test]
+    fn test_builder_dead_glyph_background() {
+        let grid = GridBuilder::new()
+            .size(2, 1)
+            .dead_glyph('.')
+            .build()
+            .unwrap();
+        assert_eq!(grid.background, CellState::Dead('.'));
+        assert_eq!(grid.with_ghost_border().to_string(), "....\n....\n....\n");
+    }
+
+    #[test]
+    fn test_builder_invalid_size() {
+        assert_eq!(
+            GridBuilder::new().size(0, 4).build().unwrap_err(),
+            GridError::ZeroWidth { height: 4 }
+        );
+    }
+
+    #[test]
+    fn test_builder_invalid_range() {
+        assert_eq!(
+            GridBuilder::new().range(0).build().unwrap_err(),
+            GridError::InvalidRange { range: 0 }
+        );
+        assert_eq!(GridBuilder::new().range(5).build().unwrap().range(), 5);
+    }
+}
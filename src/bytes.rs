@@ -0,0 +1,111 @@
+//! Converting grids to and from plain data such as one byte per cell
+
+use alloc::vec::Vec;
+
+use crate::{CellState, Grid, GridError};
+
+impl Grid<CellState> {
+    /// Returns the state of every cell in row-major order as one byte each, `1` for alive and
+    /// `0` otherwise. Glyphs are not included.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii("O.\n.O", 'O', '.');
+    /// assert_eq!(grid.as_bytes(), vec![1, 0, 0, 1]);
+    /// ```
+    pub fn as_bytes(&self) -> Vec<u8> {
+        self.cells.iter().map(|c| u8::from(c.is_alive())).collect()
+    }
+
+    /// Create a new `Grid` of a given `width` and `height` from one byte per cell in row-major
+    /// order, as produced by `as_bytes`. Any non-zero byte is a live cell.
+    /// Fails if the number of bytes does not match the size, or if the size is not usable as
+    /// with `Grid::try_new`.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_bytes(&[0, 1, 1, 0], 2, 2).unwrap();
+    /// assert_eq!(grid.population, 2);
+    /// ```
+    pub fn from_bytes(bytes: &[u8], width: usize, height: usize) -> Result<Self, GridError> {
+        let mut grid = Self::try_new(width, height)?;
+        if bytes.len() != grid.cells.len() {
+            return Err(GridError::SizeMismatch {
+                expected: grid.cells.len(),
+                found: bytes.len(),
+            });
+        }
+        for (cell, &byte) in grid.cells.iter_mut().zip(bytes) {
+            if byte != 0 {
+                *cell = CellState::Alive(grid.alive_glyph);
+            }
+        }
+        grid.population = grid.calculate_population();
+        Ok(grid)
+    }
+
+    /// Returns `height` rows of `width` booleans, `true` for live cells and `false` otherwise.
+    /// Glyphs are not included.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii("O..\n.OO", 'O', '.');
+    /// assert_eq!(grid.to_bool_matrix(), vec![vec![true, false, false], vec![false, true, true]]);
+    /// ```
+    pub fn to_bool_matrix(&self) -> Vec<Vec<bool>> {
+        (0..self.height)
+            .map(|y| {
+                let row = &self.cells[y * self.width..(y + 1) * self.width];
+                row.iter().map(|c| c.is_alive()).collect()
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bytes_round_trip() {
+        let mut g = Grid::from_ascii("O..O.\n.OO..\n....O", 'O', '.');
+        g.states = 3;
+        g.update_states();
+        let bytes = g.as_bytes();
+        assert_eq!(bytes.len(), 15);
+        assert!(bytes.iter().all(|&b| b <= 1));
+
+        let copy = Grid::from_bytes(&bytes, g.width, g.height).unwrap();
+        assert_eq!(copy.live_points(), g.live_points());
+        assert_eq!(copy.population, g.population);
+        assert_eq!(copy.as_bytes(), bytes);
+    }
+
+    #[test]
+    fn test_to_bool_matrix() {
+        let mut g = Grid::new_empty(2, 3);
+        g.cells[5] = CellState::Dying('+', 1);
+        g.cells[2] = CellState::Alive('X');
+        assert_eq!(
+            g.to_bool_matrix(),
+            [[false, false], [true, false], [false, false]]
+        );
+        assert_eq!(
+            Grid::new_empty(0, 0).to_bool_matrix(),
+            Vec::<Vec<bool>>::new()
+        );
+    }
+
+    #[test]
+    fn test_from_bytes_errors() {
+        assert_eq!(
+            Grid::from_bytes(&[1, 0, 1], 2, 2).unwrap_err(),
+            GridError::SizeMismatch {
+                expected: 4,
+                found: 3
+            }
+        );
+        assert_eq!(
+            Grid::from_bytes(&[], 0, 2).unwrap_err(),
+            GridError::ZeroWidth { height: 2 }
+        );
+        assert_eq!(Grid::from_bytes(&[7, 0], 2, 1).unwrap().population, 1);
+    }
+}
@@ -0,0 +1,165 @@
+//! Multi-colour variants where each live cell belongs to a team
+
+use alloc::vec::Vec;
+
+use crate::{CellState, Grid, Point};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+/// `ColorMode` controls which glyph a live cell is given, letting the glyph of `Alive` act as
+/// the colour of the team the cell belongs to
+/// ```
+/// use gridlife::{CellState, ColorMode, Grid};
+/// let mut grid = Grid::from_ascii("...\nABA\n...", 'A', '.');
+/// grid.cells[4] = CellState::Alive('B');
+/// grid.color_mode = ColorMode::Immigration;
+/// grid.update_states();
+/// assert_eq!(grid.to_string(), ".A.\n.B.\n.A.\n");
+/// ```
+pub enum ColorMode {
+    /// Every live cell uses the grid's `alive_glyph`
+    Single,
+    /// The Immigration game: live cells keep their glyph while they survive, and a newborn
+    /// cell takes the most common glyph among its live neighbours. Under the usual rules a
+    /// birth has exactly three parents, so with two colours there is always a majority.
+    Immigration,
+    /// QuadLife: like `Immigration` but with the four glyphs in the palette. A newborn cell
+    /// takes the most common glyph among its live neighbours, or if its neighbours all have
+    /// different glyphs, the first glyph in the palette that none of them have.
+    QuadLife([char; 4]),
+}
+
+impl Default for ColorMode {
+    fn default() -> Self {
+        ColorMode::Single
+    }
+}
+
+/// Returns the most common item, with ties going to the item seen first
+pub(crate) fn majority<T: PartialEq>(items: impl IntoIterator<Item = T>) -> Option<T> {
+    let mut counts: Vec<(T, usize)> = Vec::new();
+    for item in items {
+        match counts.iter_mut().find(|(seen, _)| *seen == item) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((item, 1)),
+        }
+    }
+    // `max_by_key` keeps the last maximum, so search from the back to favour the first
+    counts
+        .into_iter()
+        .rev()
+        .max_by_key(|&(_, count)| count)
+        .map(|(item, _)| item)
+}
+
+impl Grid<CellState> {
+    /// Replaces the glyph of every live cell in `next` according to the `color_mode`
+    pub(crate) fn apply_colors(&self, next: &mut [CellState]) {
+        if self.color_mode == ColorMode::Single {
+            return;
+        }
+        for (idx, cell) in next.iter_mut().enumerate() {
+            if !cell.is_alive() {
+                continue;
+            }
+            *cell = match self.cells[idx] {
+                CellState::Alive(glyph) => CellState::Alive(glyph),
+                _ => CellState::Alive(self.birth_glyph(idx)),
+            };
+        }
+    }
+
+    /// The glyph of a cell born at `idx`, taken from its live neighbours.
+    /// Falls back to the `alive_glyph` if it has no live neighbours on the grid.
+    fn birth_glyph(&self, idx: usize) -> char {
+        let point = self.pos(idx);
+        let parents: Vec<char> = Point::DIRECTIONS
+            .into_iter()
+            .filter_map(|dir| {
+                let neighbour = self
+                    .topology
+                    .resolve(point + dir, self.width, self.height)?;
+                match self[neighbour] {
+                    CellState::Alive(glyph) => Some(glyph),
+                    _ => None,
+                }
+            })
+            .collect();
+        if let ColorMode::QuadLife(palette) = self.color_mode {
+            let all_differ = parents
+                .iter()
+                .enumerate()
+                .all(|(i, glyph)| !parents[..i].contains(glyph));
+            if parents.len() > 1 && all_differ {
+                if let Some(&missing) = palette.iter().find(|c| !parents.contains(c)) {
+                    return missing;
+                }
+            }
+        }
+        majority(parents).unwrap_or(self.alive_glyph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_majority() {
+        assert_eq!(majority(['a', 'b', 'b']), Some('b'));
+        assert_eq!(majority(['a', 'b', 'c', 'b', 'a']), Some('a'));
+        assert_eq!(majority(Vec::<char>::new()), None);
+    }
+
+    #[test]
+    fn test_immigration_birth_takes_majority() {
+        // .A.
+        // B.B
+        // ...
+        let mut g = Grid::from_ascii(".A.\nB.B\n...", 'A', '.');
+        g.cells[3] = CellState::Alive('B');
+        g.cells[5] = CellState::Alive('B');
+        g.color_mode = ColorMode::Immigration;
+        g.update_states();
+        assert_eq!(g.cells[4], CellState::Alive('B'));
+        assert_eq!(g.cells[1], CellState::Alive('A'));
+        assert_eq!(g.population, 2);
+    }
+
+    #[test]
+    fn test_immigration_survivors_keep_color() {
+        let mut g = Grid::from_ascii("....\n.AB.\n.BB.\n....", 'A', '.');
+        for idx in [6, 9, 10] {
+            g.cells[idx] = CellState::Alive('B');
+        }
+        g.color_mode = ColorMode::Immigration;
+        g.update_states();
+        assert_eq!(g.to_string(), "....\n.AB.\n.BB.\n....\n");
+
+        g.color_mode = ColorMode::Single;
+        g.update_states();
+        assert_eq!(g.to_string(), "....\n.AA.\n.AA.\n....\n");
+    }
+
+    #[test]
+    fn test_quadlife_birth() {
+        let palette = ['A', 'B', 'C', 'D'];
+        // Three different parents give the remaining colour
+        let mut g = Grid::from_ascii(".B.\nC.D\n...", 'A', '.');
+        g.cells[1] = CellState::Alive('B');
+        g.cells[3] = CellState::Alive('C');
+        g.cells[5] = CellState::Alive('D');
+        g.color_mode = ColorMode::QuadLife(palette);
+        g.update_states();
+        assert_eq!(g.cells[4], CellState::Alive('A'));
+
+        // Otherwise the majority colour wins
+        let mut g = Grid::from_ascii(".B.\nC.C\n...", 'A', '.');
+        g.cells[1] = CellState::Alive('B');
+        g.cells[3] = CellState::Alive('C');
+        g.cells[5] = CellState::Alive('C');
+        g.color_mode = ColorMode::QuadLife(palette);
+        g.update_states();
+        assert_eq!(g.cells[4], CellState::Alive('C'));
+    }
+}
@@ -0,0 +1,122 @@
+//! Connected components of live cells
+
+use alloc::{vec, vec::Vec};
+
+use crate::{CellState, Grid, Point};
+
+const ORTHOGONAL_DIR: [Point; 4] = [Point::NORTH, Point::EAST, Point::SOUTH, Point::WEST];
+
+impl Grid<CellState> {
+    /// Labels each live cell with the connected component it belongs to, in row-major order.
+    /// Live cells touching in any of the eight directions are part of the same component.
+    /// Components are numbered from `0` in the order they are first found scanning the grid,
+    /// and dead cells are labelled `None`.
+    /// ```
+    /// use gridlife::{CellState, Grid};
+    /// let mut grid = Grid::new_empty(3, 1);
+    /// grid.cells[0] = CellState::Alive('X');
+    /// grid.cells[2] = CellState::Alive('X');
+    /// assert_eq!(grid.component_labels(), vec![Some(0), None, Some(1)]);
+    /// ```
+    pub fn component_labels(&self) -> Vec<Option<usize>> {
+        let mut labels = vec![None; self.cells.len()];
+        let mut next = 0;
+        for start in 0..self.cells.len() {
+            if labels[start].is_some() || !self.cells[start].is_alive() {
+                continue;
+            }
+            labels[start] = Some(next);
+            let mut stack = vec![self.pos(start)];
+            while let Some(point) = stack.pop() {
+                for neighbour in Point::DIRECTIONS.into_iter().map(|d| point + d) {
+                    if !self.contains(&neighbour) {
+                        continue;
+                    }
+                    let idx = self.idx(&neighbour);
+                    if labels[idx].is_none() && self.cells[idx].is_alive() {
+                        labels[idx] = Some(next);
+                        stack.push(neighbour);
+                    }
+                }
+            }
+            next += 1;
+        }
+        labels
+    }
+
+    /// Measures how much the components labelled `comp_a` and `comp_b` by `component_labels`
+    /// touch each other.
+    ///
+    /// Live cells that touch always belong to the same component, so distinct components are
+    /// always separated by dead cells. The interface is the number of dead cells that are
+    /// orthogonally adjacent to both components, i.e. the length of the gap between them.
+    /// ```
+    /// use gridlife::{CellState, Grid};
+    /// let mut grid = Grid::new_empty(3, 1);
+    /// grid.cells[0] = CellState::Alive('X');
+    /// grid.cells[2] = CellState::Alive('X');
+    /// assert_eq!(grid.interface_length(0, 1), 1);
+    /// ```
+    pub fn interface_length(&self, comp_a: usize, comp_b: usize) -> usize {
+        if comp_a == comp_b {
+            return 0;
+        }
+        let labels = self.component_labels();
+        (0..self.cells.len())
+            .filter(|&idx| !self.cells[idx].is_alive())
+            .filter(|&idx| {
+                let point = self.pos(idx);
+                let touches = |comp: usize| {
+                    ORTHOGONAL_DIR
+                        .into_iter()
+                        .map(|d| point + d)
+                        .any(|p| self.contains(&p) && labels[self.idx(&p)] == Some(comp))
+                };
+                touches(comp_a) && touches(comp_b)
+            })
+            .count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{CellState, Grid};
+
+    fn grid_with(width: usize, height: usize, alive: &[(usize, usize)]) -> Grid<CellState> {
+        let mut g = Grid::new_empty(width, height);
+        for &(x, y) in alive {
+            g.cells[y * width + x] = CellState::Alive('X');
+        }
+        g
+    }
+
+    #[test]
+    fn test_component_labels() {
+        // O..
+        // .O.
+        // ...
+        // OOO
+        let g = grid_with(3, 4, &[(0, 0), (1, 1), (0, 3), (1, 3), (2, 3)]);
+        let labels = g.component_labels();
+        assert_eq!(labels[0], Some(0));
+        assert_eq!(labels[4], Some(0));
+        assert_eq!(labels[9..], [Some(1), Some(1), Some(1)]);
+        assert_eq!(labels.iter().filter(|l| l.is_none()).count(), 7);
+    }
+
+    #[test]
+    fn test_interface_length() {
+        // Two vertical bars separated by a single dead column
+        let g = grid_with(5, 5, &[(1, 1), (1, 2), (1, 3), (3, 1), (3, 2), (3, 3)]);
+        assert_eq!(g.interface_length(0, 1), 3);
+        assert_eq!(g.interface_length(1, 0), 3);
+        assert_eq!(g.interface_length(0, 0), 0);
+        assert_eq!(g.interface_length(0, 7), 0);
+    }
+
+    #[test]
+    fn test_interface_length_distant_components() {
+        let g = grid_with(6, 1, &[(0, 0), (5, 0)]);
+        assert_eq!(g.interface_length(0, 1), 0);
+    }
+}
@@ -0,0 +1,221 @@
+//! Detecting when a grid returns to an earlier state
+
+use std::{
+    collections::{hash_map::DefaultHasher, HashMap},
+    hash::{Hash, Hasher},
+};
+
+use crate::{CellState, Grid};
+
+impl Grid<CellState> {
+    /// Hashes the state of every cell along with the grid's dimensions, counting `Dying` cells
+    /// by how many generations they have left. Glyphs, ages and any other state are ignored, so
+    /// grids with the same pattern always produce the same hash. Different patterns may still
+    /// collide.
+    /// ```
+    /// use gridlife::Grid;
+    /// let a = Grid::from_ascii("O.\n.O", 'O', '.');
+    /// let b = Grid::from_ascii("#-\n-#", '#', '-');
+    /// assert_eq!(a.state_hash(), b.state_hash());
+    /// ```
+    pub fn state_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.width.hash(&mut hasher);
+        self.height.hash(&mut hasher);
+        for cell in &self.cells {
+            state_key(cell).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    fn pattern(&self) -> Vec<u16> {
+        self.cells.iter().map(state_key).collect()
+    }
+}
+
+/// Number identifying the state of a cell without its glyph, so `Dying` cells with different
+/// countdowns are told apart
+fn state_key(cell: &CellState) -> u16 {
+    match cell {
+        CellState::Dead(_) => 0,
+        CellState::Alive(_) => 1,
+        CellState::Dying(_, left) => 2 + u16::from(*left),
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `StableResult` reports how a run of `Grid::run_until_stable` ended
+pub enum StableResult {
+    /// The grid stopped changing, detected after the given number of generations
+    Stable(usize),
+    /// The grid returned to an earlier state `period` generations apart, detected after `gen`
+    /// generations
+    Oscillating {
+        /// Number of generations between repeats
+        period: usize,
+        /// Number of generations run when the repeat was detected
+        gen: usize,
+    },
+    /// No repeated state was found within the generation limit
+    GaveUp,
+}
+
+impl Grid<CellState> {
+    /// Advances the grid until it returns to an earlier state, or until `max_gen` generations
+    /// have been run. Every state seen is remembered, so this also detects oscillators of any
+    /// period, at the cost of memory proportional to the number of generations run.
+    /// The grid is left at its final state.
+    /// ```
+    /// use gridlife::{Grid, StableResult};
+    /// let mut block = Grid::from_ascii("....\n.OO.\n.OO.\n....", 'O', '.');
+    /// assert_eq!(block.run_until_stable(10), StableResult::Stable(1));
+    /// let mut blinker = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+    /// assert_eq!(
+    ///     blinker.run_until_stable(10),
+    ///     StableResult::Oscillating { period: 2, gen: 2 }
+    /// );
+    /// ```
+    pub fn run_until_stable(&mut self, max_gen: usize) -> StableResult {
+        let mut detector = CycleDetector::new();
+        detector.observe(self);
+        for gen in 1..=max_gen {
+            self.update_states();
+            match detector.observe(self) {
+                Some(1) => return StableResult::Stable(gen),
+                Some(period) => return StableResult::Oscillating { period, gen },
+                None => {}
+            }
+        }
+        StableResult::GaveUp
+    }
+}
+
+#[derive(Debug, Default, Clone)]
+/// `CycleDetector` remembers the states a grid has been in and reports when it returns to one.
+///
+/// States are looked up by `Grid::state_hash`. As different states can share a hash, a hash
+/// match is only reported as a repeat once the state of every cell has been compared, so
+/// collisions never produce a false positive; they only cost an extra comparison.
+/// ```
+/// use gridlife::{CycleDetector, Grid};
+/// let mut grid = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+/// let mut detector = CycleDetector::new();
+/// assert_eq!(detector.observe(&grid), None);
+/// grid.update_states();
+/// assert_eq!(detector.observe(&grid), None);
+/// grid.update_states();
+/// assert_eq!(detector.observe(&grid), Some(2));
+/// ```
+pub struct CycleDetector {
+    seen: HashMap<u64, Vec<(usize, Vec<u16>)>>,
+    generation: usize,
+}
+
+impl CycleDetector {
+    /// Create a new `CycleDetector` that has not seen any states
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the state of `grid` as the next generation.
+    /// If the same state has been observed before, returns the number of generations since it
+    /// was last seen, i.e. the period of the cycle.
+    pub fn observe(&mut self, grid: &Grid<CellState>) -> Option<usize> {
+        let generation = self.generation;
+        self.generation += 1;
+        let pattern = grid.pattern();
+        let states = self.seen.entry(grid.state_hash()).or_default();
+        let period = states
+            .iter_mut()
+            .find(|(_, seen)| *seen == pattern)
+            .map(|(last, _)| generation - std::mem::replace(last, generation));
+        if period.is_none() {
+            states.push((generation, pattern));
+        }
+        period
+    }
+
+    /// Forget every state observed so far
+    pub fn clear(&mut self) {
+        self.seen.clear();
+        self.generation = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_state_hash_ignores_glyphs() {
+        let a = Grid::from_ascii("O.\n.O", 'O', '.');
+        let b = Grid::from_ascii("X \n X", 'X', ' ');
+        let c = Grid::from_ascii("OO\n.O", 'O', '.');
+        assert_eq!(a.state_hash(), b.state_hash());
+        assert_ne!(a.state_hash(), c.state_hash());
+    }
+
+    #[test]
+    fn test_cycle_detector_still_life() {
+        let mut grid = Grid::from_ascii("....\n.OO.\n.OO.\n....", 'O', '.');
+        let mut detector = CycleDetector::new();
+        assert_eq!(detector.observe(&grid), None);
+        grid.update_states();
+        assert_eq!(detector.observe(&grid), Some(1));
+    }
+
+    #[test]
+    fn test_cycle_detector_confirms_collisions() {
+        let a = Grid::from_ascii("O.", 'O', '.');
+        let b = Grid::from_ascii(".O", 'O', '.');
+        let mut detector = CycleDetector::new();
+        // Force a collision by filing a different pattern under the same hash
+        detector.seen.insert(a.state_hash(), vec![(0, b.pattern())]);
+        detector.generation = 1;
+        assert_eq!(detector.observe(&a), None);
+        assert_eq!(detector.observe(&a), Some(1));
+    }
+
+    #[test]
+    fn test_state_hash_counts_dying_cells() {
+        let mut a = Grid::from_ascii("O.", 'O', '.');
+        let mut b = a.clone();
+        a.cells[0] = CellState::Dying('+', 1);
+        b.cells[0] = CellState::Dying('+', 2);
+        assert_ne!(a.state_hash(), b.state_hash());
+        a.cells[0] = CellState::Dead('.');
+        assert_ne!(a.state_hash(), b.state_hash());
+    }
+
+    #[test]
+    fn test_run_until_stable_generations() {
+        // A lone cell dies at once under Conway, but with four states it spends two
+        // generations dying before the grid stops changing
+        let mut g = Grid::from_ascii("...\n.O.\n...", 'O', '.');
+        g.states = 4;
+        assert_eq!(g.run_until_stable(10), StableResult::Stable(4));
+        assert!(g.cells.iter().all(CellState::is_dead));
+    }
+
+    #[test]
+    fn test_cycle_detector_clear() {
+        let grid = Grid::from_ascii("O", 'O', '.');
+        let mut detector = CycleDetector::new();
+        detector.observe(&grid);
+        detector.clear();
+        assert_eq!(detector.observe(&grid), None);
+    }
+
+    #[test]
+    fn test_run_until_stable() {
+        let mut g = Grid::from_ascii("O.O\n...\n...", 'O', '.');
+        assert_eq!(g.run_until_stable(10), StableResult::Stable(2));
+        assert_eq!(g.population, 0);
+
+        // Glider on a wrapping grid never repeats in place within a few generations
+        let mut g = Grid::from_ascii(".O....\n..O...\nOOO...\n......\n......\n......", 'O', '.');
+        g.topology = crate::Topology::Toroidal;
+        assert_eq!(g.run_until_stable(5), StableResult::GaveUp);
+        assert_eq!(g.run_until_stable(0), StableResult::GaveUp);
+    }
+}
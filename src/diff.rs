@@ -0,0 +1,97 @@
+//! Comparing the live cells of two grids
+
+use alloc::vec::Vec;
+
+use crate::{CellState, Grid, GridError};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `Change` describes how a cell differs between two grids, see `Grid::diff`
+pub enum Change {
+    /// The cell is alive in the other grid but not in this one
+    Born,
+    /// The cell is alive in this grid but not in the other one
+    Died,
+}
+
+impl Grid<CellState> {
+    /// Returns the `(x, y)` position of every cell that is alive in only one of this grid and
+    /// `other`, in row-major order, along with whether it was born or died going from this grid
+    /// to `other`. Glyphs are ignored, and dying cells count as dead.
+    ///
+    /// Fails with `GridError::DimensionMismatch` if the grids are not the same size.
+    /// ```
+    /// use gridlife::{Change, Grid};
+    /// let grid = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+    /// let mut next = grid.clone();
+    /// next.update_states();
+    /// let changes = grid.diff(&next).unwrap();
+    /// assert_eq!(changes[0], (1, 0, Change::Born));
+    /// assert_eq!(changes[1], (0, 1, Change::Died));
+    /// ```
+    pub fn diff(&self, other: &Grid<CellState>) -> Result<Vec<(usize, usize, Change)>, GridError> {
+        if (self.width, self.height) != (other.width, other.height) {
+            return Err(GridError::DimensionMismatch {
+                expected: (self.width, self.height),
+                found: (other.width, other.height),
+            });
+        }
+        Ok(self
+            .cells
+            .iter()
+            .zip(&other.cells)
+            .enumerate()
+            .filter_map(|(idx, (before, after))| {
+                let change = match (before.is_alive(), after.is_alive()) {
+                    (false, true) => Change::Born,
+                    (true, false) => Change::Died,
+                    _ => return None,
+                };
+                Some((idx % self.width, idx / self.width, change))
+            })
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diff_with_next_generation() {
+        let grid = Grid::from_ascii(".....\n.OOO.\n.....", 'O', '.');
+        let mut next = grid.clone();
+        next.update_states();
+        assert_eq!(
+            grid.diff(&next),
+            Ok(vec![
+                (2, 0, Change::Born),
+                (1, 1, Change::Died),
+                (3, 1, Change::Died),
+                (2, 2, Change::Born),
+            ])
+        );
+        assert_eq!(grid.diff(&grid), Ok(vec![]));
+        // The blinker flips back, so going the other way swaps every birth and death
+        assert_eq!(
+            next.diff(&grid),
+            Ok(vec![
+                (2, 0, Change::Died),
+                (1, 1, Change::Born),
+                (3, 1, Change::Born),
+                (2, 2, Change::Died),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_diff_dimension_mismatch() {
+        let grid = Grid::new_empty(3, 4);
+        assert_eq!(
+            grid.diff(&Grid::new_empty(4, 3)),
+            Err(GridError::DimensionMismatch {
+                expected: (3, 4),
+                found: (4, 3),
+            })
+        );
+    }
+}
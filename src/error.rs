@@ -0,0 +1,245 @@
+use core::fmt;
+
+use crate::{Coord, MAX_RANGE};
+#[cfg(feature = "std")]
+use std::{error::Error, io};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+/// `GridError` describes why a `Grid` could not be created or accessed
+pub enum GridError {
+    /// The grid has a `width` of zero but a non-zero `height`
+    ZeroWidth {
+        /// The requested `height`
+        height: usize,
+    },
+    /// The number of cells in the grid, or one of its dimensions, cannot be represented
+    TooLarge {
+        /// The requested `width`
+        width: usize,
+        /// The requested `height`
+        height: usize,
+    },
+    /// The number of cells given does not match the requested `width` and `height`
+    SizeMismatch {
+        /// The number of cells needed for the requested size
+        expected: usize,
+        /// The number of cells given
+        found: usize,
+    },
+    /// Two grids that must be the same size have different dimensions
+    DimensionMismatch {
+        /// The `width` and `height` of the grid being compared against
+        expected: (usize, usize),
+        /// The `width` and `height` of the other grid
+        found: (usize, usize),
+    },
+    /// A cell was accessed outside the bounds of the grid
+    OutOfBounds {
+        /// Column of the requested cell
+        x: Coord,
+        /// Row of the requested cell
+        y: Coord,
+    },
+    /// The alive and dead glyphs are the same, or one of them is a control character
+    InvalidGlyphs {
+        /// The requested alive glyph
+        alive: char,
+        /// The requested dead glyph
+        dead: char,
+    },
+    /// The neighbour `range` is zero or too large for a `Ruleset` to count, see `Grid::set_range`
+    InvalidRange {
+        /// The requested range
+        range: u32,
+    },
+}
+
+impl fmt::Display for GridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GridError::ZeroWidth { height } => {
+                write!(f, "grid has zero width but a height of {height}")
+            }
+            GridError::TooLarge { width, height } => {
+                write!(f, "grid of {width}x{height} cells is too large")
+            }
+            GridError::SizeMismatch { expected, found } => {
+                write!(f, "expected {expected} cells but found {found}")
+            }
+            GridError::DimensionMismatch { expected, found } => {
+                write!(
+                    f,
+                    "expected a {}x{} grid but found {}x{}",
+                    expected.0, expected.1, found.0, found.1
+                )
+            }
+            GridError::OutOfBounds { x, y } => write!(f, "cell ({x}, {y}) is outside the grid"),
+            GridError::InvalidGlyphs { alive, dead } if alive == dead => {
+                write!(f, "alive and dead glyphs are both {alive:?}")
+            }
+            GridError::InvalidGlyphs { alive, dead } => {
+                write!(
+                    f,
+                    "glyphs {alive:?} and {dead:?} include a control character"
+                )
+            }
+            GridError::InvalidRange { range } => {
+                write!(
+                    f,
+                    "neighbour range {range} is not between 1 and {MAX_RANGE}"
+                )
+            }
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for GridError {}
+
+#[derive(PartialEq, Eq, Clone, Debug)]
+#[non_exhaustive]
+/// `ParseError` describes why a pattern could not be parsed into a `Grid`
+pub enum ParseError {
+    /// The input does not contain any pattern rows
+    Empty,
+    /// An unexpected character was found in the pattern
+    InvalidChar {
+        /// Line of the input the character is on, starting at 1
+        line: usize,
+        /// Column of the character within the line, starting at 1
+        column: usize,
+        /// The unexpected character
+        found: char,
+    },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "pattern does not contain any cells"),
+            ParseError::InvalidChar {
+                line,
+                column,
+                found,
+            } => write!(f, "unexpected character {found:?} at {line}:{column}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for ParseError {}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+/// `RuleError` describes why a rule string could not be parsed into a `Ruleset`
+pub enum RuleError {
+    /// The rule is not of the form `B3/S23` or `23/3`
+    InvalidFormat,
+    /// A neighbour count is not a digit from 0 to 8
+    InvalidCount(char),
+}
+
+impl fmt::Display for RuleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleError::InvalidFormat => write!(f, "rule is not of the form B3/S23"),
+            RuleError::InvalidCount(c) => write!(f, "invalid neighbour count {c:?} in rule"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for RuleError {}
+
+#[cfg(feature = "std")]
+#[derive(Debug)]
+#[non_exhaustive]
+/// `RleError` describes why a run length encoded pattern could not be read into a `Grid`
+pub enum RleError {
+    /// Reading the input failed
+    Io(io::Error),
+    /// The input ended before the `x = .., y = ..` header line
+    MissingHeader,
+    /// The header line does not give both the `x` and `y` dimensions
+    InvalidHeader {
+        /// Line of the input the header is on, starting at 1
+        line: usize,
+    },
+    /// The `rule` given in the header could not be parsed
+    InvalidRule {
+        /// Line of the input the header is on, starting at 1
+        line: usize,
+        /// The rule as written in the header
+        rule: String,
+        /// Why the rule could not be parsed
+        error: RuleError,
+    },
+    /// An unexpected character was found in the pattern
+    InvalidChar {
+        /// Line of the input the character is on, starting at 1
+        line: usize,
+        /// Column of the character within the line, starting at 1
+        column: usize,
+        /// The unexpected character
+        found: char,
+    },
+    /// A run of live cells extends beyond the size given in the header
+    OutOfBounds {
+        /// Line of the input the run ends on, starting at 1
+        line: usize,
+        /// Column of the run's tag within the line, starting at 1
+        column: usize,
+    },
+    /// The size given in the header is not a valid grid
+    Grid(GridError),
+}
+
+#[cfg(feature = "std")]
+impl fmt::Display for RleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RleError::Io(e) => write!(f, "failed to read pattern: {e}"),
+            RleError::MissingHeader => write!(f, "pattern is missing the `x = .., y = ..` header"),
+            RleError::InvalidHeader { line } => write!(f, "invalid header on line {line}"),
+            RleError::InvalidRule { line, rule, error } => {
+                write!(f, "invalid rule {rule:?} on line {line}: {error}")
+            }
+            RleError::InvalidChar {
+                line,
+                column,
+                found,
+            } => write!(f, "unexpected character {found:?} at {line}:{column}"),
+            RleError::OutOfBounds { line, column } => {
+                write!(f, "cells at {line}:{column} are outside the pattern size")
+            }
+            RleError::Grid(e) => write!(f, "invalid pattern size: {e}"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for RleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RleError::Io(e) => Some(e),
+            RleError::InvalidRule { error, .. } => Some(error),
+            RleError::Grid(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for RleError {
+    fn from(e: io::Error) -> Self {
+        RleError::Io(e)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<GridError> for RleError {
+    fn from(e: GridError) -> Self {
+        RleError::Grid(e)
+    }
+}
@@ -0,0 +1,175 @@
+//! Explaining why each cell changes, for debugging rules
+
+use alloc::vec::Vec;
+
+use crate::{rules::MAX_COUNT, CellState, Grid, Ruleset};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+/// `DeathReason` describes why a live cell did not survive
+pub enum DeathReason {
+    /// The cell has fewer live neighbours than any count that allows survival
+    Underpopulation,
+    /// The cell has more live neighbours than any count that allows survival
+    Overpopulation,
+    /// The cell's live neighbour count lies between counts that allow survival, or the rules do
+    /// not allow survival at all
+    NotSurvival,
+    /// The grid steps with a custom `Rule` from `Grid::set_rule`, which gives no reason
+    CustomRule,
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+/// `Transition` names the branch of the rules that decided a cell's next state
+pub enum Transition {
+    /// A dead cell was born
+    Birth,
+    /// A live cell survived
+    Survive,
+    /// A live cell died, or started dying when the grid has more than two `states`
+    Death(DeathReason),
+    /// A dying cell moved one generation closer to being dead
+    Decay,
+    /// A dead cell stayed dead
+    StayDead,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+/// `CellTransition` records how one cell is updated by a step, see `Grid::explain_step`
+pub struct CellTransition {
+    /// State of the cell before the step
+    pub old: CellState,
+    /// State of the cell after the step, ignoring the grid's `color_mode`
+    pub new: CellState,
+    /// Number of live neighbours the cell had
+    pub alive_neighbours: u32,
+    /// The branch of the rules that decided the new state
+    pub transition: Transition,
+}
+
+/// Why a live cell with `alive` live neighbours does not survive under `rules`
+fn death_reason(rules: &Ruleset, alive: u32) -> DeathReason {
+    let survival: Vec<u32> = (0..=MAX_COUNT).filter(|&n| rules.is_survival(n)).collect();
+    match (survival.first(), survival.last()) {
+        (Some(&min), _) if alive < min => DeathReason::Underpopulation,
+        (_, Some(&max)) if alive > max => DeathReason::Overpopulation,
+        _ => DeathReason::NotSurvival,
+    }
+}
+
+impl Grid<CellState> {
+    /// Works out the next generation like `update_states` without changing the grid, returning
+    /// for every cell in row-major order its live neighbour count and which rule fired.
+    ///
+    /// This is much slower than stepping, so it is meant for tracking down why a ruleset does
+    /// not behave as expected.
+    /// When a custom `Rule` is set the transitions follow its output, but deaths are reported as
+    /// `DeathReason::CustomRule` since only a `Ruleset` can say why a cell died.
+    /// ```
+    /// use gridlife::{DeathReason, Grid, Transition};
+    /// let grid = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+    /// let steps = grid.explain_step();
+    /// assert_eq!(steps[1].transition, Transition::Birth);
+    /// assert_eq!(steps[3].transition, Transition::Death(DeathReason::Underpopulation));
+    /// assert_eq!((steps[4].alive_neighbours, steps[4].transition), (2, Transition::Survive));
+    /// ```
+    pub fn explain_step(&self) -> Vec<CellTransition> {
+        self.cells
+            .iter()
+            .enumerate()
+            .map(|(idx, &old)| {
+                let state = self.get_neighbours_state(self.pos(idx));
+                let alive = state.alive as u32;
+                let new = self.get_cell_state(&old, state);
+                let transition = match (old, new) {
+                    (CellState::Alive(_), CellState::Alive(_)) => Transition::Survive,
+                    (CellState::Alive(_), _) => Transition::Death(match self.rule {
+                        Some(_) => DeathReason::CustomRule,
+                        None => death_reason(&self.rules, alive),
+                    }),
+                    (CellState::Dying(..), _) => Transition::Decay,
+                    (CellState::Dead(_), CellState::Alive(_)) => Transition::Birth,
+                    (CellState::Dead(_), _) => Transition::StayDead,
+                };
+                CellTransition {
+                    old,
+                    new,
+                    alive_neighbours: alive,
+                    transition,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_death_reason() {
+        let conway = Ruleset::conway();
+        assert_eq!(death_reason(&conway, 1), DeathReason::Underpopulation);
+        assert_eq!(death_reason(&conway, 4), DeathReason::Overpopulation);
+        let gappy = Ruleset::new(&[3], &[1, 5]);
+        assert_eq!(death_reason(&gappy, 3), DeathReason::NotSurvival);
+        let seeds = Ruleset::new(&[2], &[]);
+        assert_eq!(death_reason(&seeds, 0), DeathReason::NotSurvival);
+    }
+
+    #[test]
+    fn test_explain_step_matches_update_states() {
+        let mut g = Grid::from_ascii("OO..\nOO.O\n..OO\nO...", 'O', '.');
+        g.states = 3;
+        for _ in 0..3 {
+            let steps = g.explain_step();
+            g.update_states();
+            assert_eq!(steps.len(), g.cells.len());
+            for (step, cell) in steps.iter().zip(&g.cells) {
+                assert_eq!(step.new, *cell);
+            }
+        }
+    }
+
+    #[test]
+    fn test_explain_step_custom_rule() {
+        #[derive(Debug)]
+        struct AlwaysDead;
+        impl crate::Rule for AlwaysDead {
+            fn next(&self, _: CellState, _: u32) -> CellState {
+                CellState::Dead('.')
+            }
+        }
+        let mut g = Grid::from_ascii(".O.\nOOO\n.O.", 'O', '.');
+        g.set_rule(alloc::boxed::Box::new(AlwaysDead));
+        let steps = g.explain_step();
+        assert_eq!(
+            steps[4].transition,
+            Transition::Death(DeathReason::CustomRule)
+        );
+        assert_eq!(steps[0].transition, Transition::StayDead);
+    }
+
+    #[test]
+    fn test_death_reason_large_counts() {
+        let rules = Ruleset::new(&[], &[20, 24]);
+        assert_eq!(death_reason(&rules, 10), DeathReason::Underpopulation);
+        assert_eq!(death_reason(&rules, 22), DeathReason::NotSurvival);
+    }
+
+    #[test]
+    fn test_explain_step_decay() {
+        let mut g = Grid::from_ascii("O", 'O', '.');
+        g.states = 3;
+        assert_eq!(
+            g.explain_step()[0].transition,
+            Transition::Death(DeathReason::Underpopulation)
+        );
+        g.update_states();
+        let step = g.explain_step()[0];
+        assert_eq!(step.old, CellState::Dying('+', 1));
+        assert_eq!(step.transition, Transition::Decay);
+        assert_eq!(step.new, CellState::Dead('.'));
+    }
+}
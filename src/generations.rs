@@ -0,0 +1,67 @@
+//! Iterating over successive generations of a grid
+
+use core::iter::FusedIterator;
+
+use crate::{CellState, Grid};
+
+#[derive(Debug, Clone)]
+/// `Generations` is an endless iterator over the successive generations of a `Grid`,
+/// created by `Grid::generations`.
+///
+/// Each item is a clone of the grid, so this is convenient for analysis but slower than calling
+/// `Grid::update_states` in a loop.
+pub struct Generations {
+    grid: Grid<CellState>,
+}
+
+impl Iterator for Generations {
+    type Item = Grid<CellState>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.grid.update_states();
+        Some(self.grid.clone())
+    }
+}
+
+impl FusedIterator for Generations {}
+
+impl Grid<CellState> {
+    /// Turns the grid into an iterator yielding each following generation.
+    /// The iterator never ends, so limit it with adapters such as `take` or `take_while`.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::new_random(10, 10);
+    /// let populations: Vec<usize> = grid.generations().take(5).map(|g| g.population).collect();
+    /// assert_eq!(populations.len(), 5);
+    /// ```
+    pub fn generations(self) -> Generations {
+        Generations { grid: self }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Grid;
+
+    #[test]
+    fn test_generations_blinker() {
+        let grid = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+        let states: Vec<String> = grid.generations().take(3).map(|g| g.to_string()).collect();
+        assert_eq!(
+            states,
+            vec![".O.\n.O.\n.O.\n", "...\nOOO\n...\n", ".O.\n.O.\n.O.\n"]
+        );
+    }
+
+    #[test]
+    fn test_generations_take_while() {
+        // A lone cell dies immediately
+        let grid = Grid::from_ascii("O..", 'O', '.');
+        let alive = grid
+            .generations()
+            .take(10)
+            .take_while(|g| g.population > 0)
+            .count();
+        assert_eq!(alive, 0);
+    }
+}
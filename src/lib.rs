@@ -1,3 +1,4 @@
+#![cfg_attr(not(feature = "std"), no_std)]
 #![warn(missing_docs)]
 //! Game of Life
 //!
@@ -6,43 +7,133 @@
 //! See: <https://en.wikipedia.org/wiki/Conway%27s_Game_of_Life>
 //!
 //! ```
-//! use gridlife::Grid;
+//! use gridlife::{Grid, StableResult};
 //! let mut grid = Grid::new_random(3, 3);
-//! let mut population = grid.population;
-//! // Run the rules of Game of Life until the population count stabalizes
-//! loop {
-//!     grid.update_states();
-//!     let next_pop = grid.population;
-//!     if next_pop == population {
-//!         break;
-//!     }
-//!     population = next_pop;
+//! // Run the rules of Game of Life until the grid settles down, giving up after 100 generations
+//! match grid.run_until_stable(100) {
+//!     StableResult::Stable(gen) => println!("Stable after {gen} generations"),
+//!     StableResult::Oscillating { period, .. } => println!("Oscillating with period {period}"),
+//!     StableResult::GaveUp => println!("Still changing"),
 //! }
 //! ```
-use std::{
+//!
+//! ## Features
+//!
+//! | Feature | Default | Enables |
+//! |---------|---------|---------|
+//! | `std`   | yes     | Everything that needs the standard library, listed below |
+//! | `image` | no      | `Grid::to_image`, implies `std` |
+//! | `gif`   | no      | `Grid::record_gif`, implies `image` |
+//! | `wasm`  | no      | `WasmGrid` JavaScript bindings with `wasm-bindgen`, implies `std` |
+//!
+//! Without `std` the crate is `no_std` and only needs `alloc`. The simulation itself, including
+//! `Grid`, `CellState`, `Grid::update_states` and seeded random generation with
+//! `Grid::new_random_seeded`, `Grid::randomize_seeded` or `GridBuilder::seed`, is always
+//! available. These need `std`:
+//! - unseeded random generation: `Grid::new_random`, `Grid::new_random_custom_glyphs`,
+//!   `Grid::new_random_with_rules`, `Grid::new_random_region` and `Grid::randomize`
+//! - cycle detection: `CycleDetector`, `Grid::state_hash` and `Grid::run_until_stable`
+//! - `SparseGrid`
+//! - reading RLE patterns with `Grid::from_rle_reader`
+//! - `std::error::Error` implementations for the error types
+//!
+//! ## Panics
+//!
+//! Indexing a `Grid` with `grid[point]` or `grid[(x, y)]` panics if the cell is outside the
+//! grid, like indexing a slice. `Grid::get`, `Grid::cell` and `Grid::cell_mut` return `None` or
+//! a `GridError::OutOfBounds` error instead, and `Grid::set` and `Grid::toggle` return `None`.
+//! `Grid::next_generation` panics if given the wrong number of cells, and
+//! `Grid::new_empty_custom_glyphs` and `Grid::new_random_custom_glyphs` panic if the alive and
+//! dead glyphs are the same or either is a control character.
+extern crate alloc;
+
+use alloc::{boxed::Box, collections::VecDeque, string::String, sync::Arc, vec, vec::Vec};
+use core::{
+    convert::Infallible,
     fmt::{self, Debug, Display},
-    ops::{Add, Index},
+    ops::{Add, Index, IndexMut, Neg, Sub},
+    str::FromStr,
 };
 
-type Coord = i32;
-
-const NORTH: Point = Point::new(0, -1);
-const NORTH_EAST: Point = Point::new(1, -1);
-const EAST: Point = Point::new(1, 0);
-const SOUTH_EAST: Point = Point::new(1, 1);
-const SOUTH: Point = Point::new(0, 1);
-const SOUTH_WEST: Point = Point::new(-1, 1);
-const WEST: Point = Point::new(-1, 0);
-const NORTH_WEST: Point = Point::new(-1, -1);
-
-const ORTHO_PLUS_DIR: [Point; 8] = [
-    NORTH, NORTH_EAST, EAST, SOUTH_EAST, SOUTH, SOUTH_WEST, WEST, NORTH_WEST,
-];
-
-#[derive(Copy, Clone, Debug, Eq, PartialEq)]
-struct Point {
-    x: Coord,
-    y: Coord,
+use unicode_width::UnicodeWidthChar;
+
+mod builder;
+mod bytes;
+mod color;
+mod components;
+#[cfg(feature = "std")]
+mod cycle;
+mod diff;
+mod error;
+mod explain;
+mod generations;
+mod metadata;
+mod neighbours;
+pub mod patterns;
+mod payload;
+mod plaintext;
+#[cfg(feature = "image")]
+mod render;
+mod reversible;
+#[cfg(feature = "std")]
+mod rle;
+mod rules;
+mod spaceships;
+#[cfg(feature = "std")]
+mod sparse;
+mod symmetry;
+mod topology;
+#[cfg(feature = "wasm")]
+mod wasm;
+mod world;
+
+use neighbours::NeighbourTable;
+
+pub use builder::GridBuilder;
+pub use color::ColorMode;
+#[cfg(feature = "std")]
+pub use cycle::{CycleDetector, StableResult};
+pub use diff::Change;
+#[cfg(feature = "std")]
+pub use error::RleError;
+pub use error::{GridError, ParseError, RuleError};
+pub use explain::{CellTransition, DeathReason, Transition};
+pub use generations::Generations;
+pub use metadata::PatternMetadata;
+pub use payload::PayloadGrid;
+#[cfg(feature = "image")]
+pub use render::{ALIVE_COLOR, DEAD_COLOR};
+pub use reversible::SecondOrderGrid;
+pub use rules::{expected_next_density, Rule, Ruleset};
+pub use spaceships::Spaceship;
+#[cfg(feature = "std")]
+pub use sparse::SparseGrid;
+pub use symmetry::Symmetry;
+pub use topology::Topology;
+#[cfg(feature = "wasm")]
+pub use wasm::WasmGrid;
+pub use world::World;
+
+/// Characters read as live cells by `Grid::from_str_auto`
+const AUTO_ALIVE: [char; 5] = ['X', 'O', '#', '*', '1'];
+
+/// Largest `Grid::range` whose neighbour counts a `Ruleset` can hold
+const MAX_RANGE: u32 = 5;
+
+/// Integer type used for the `x` and `y` components of a `Point`.
+///
+/// This limits the width and height of a `Grid` but not its number of cells, which only has to
+/// fit in a `usize`. `SparseGrid` is unbounded and uses `i64` coordinates instead.
+pub type Coord = i32;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// `Point` is the position of a cell on a `Grid`, or an offset between two cells.
+/// `x` increases to the east and `y` increases to the south, with `(0, 0)` the top left cell.
+pub struct Point {
+    /// Column of the cell
+    pub x: Coord,
+    /// Row of the cell
+    pub y: Coord,
 }
 impl AsRef<Point> for Point {
     fn as_ref(&self) -> &Self {
@@ -59,12 +150,131 @@ impl Add for Point {
     }
 }
 
+impl Sub for Point {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, rhs: Self) -> Self::Output {
+        Point::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl Neg for Point {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self::Output {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl From<(Coord, Coord)> for Point {
+    fn from((x, y): (Coord, Coord)) -> Self {
+        Point::new(x, y)
+    }
+}
+
 impl Point {
+    /// Offset to the cell above
+    pub const NORTH: Point = Point::new(0, -1);
+    /// Offset to the cell above and to the right
+    pub const NORTH_EAST: Point = Point::new(1, -1);
+    /// Offset to the cell to the right
+    pub const EAST: Point = Point::new(1, 0);
+    /// Offset to the cell below and to the right
+    pub const SOUTH_EAST: Point = Point::new(1, 1);
+    /// Offset to the cell below
+    pub const SOUTH: Point = Point::new(0, 1);
+    /// Offset to the cell below and to the left
+    pub const SOUTH_WEST: Point = Point::new(-1, 1);
+    /// Offset to the cell to the left
+    pub const WEST: Point = Point::new(-1, 0);
+    /// Offset to the cell above and to the left
+    pub const NORTH_WEST: Point = Point::new(-1, -1);
+    /// Offsets to all eight neighbours of a cell, clockwise from `NORTH`
+    pub const DIRECTIONS: [Point; 8] = [
+        Point::NORTH,
+        Point::NORTH_EAST,
+        Point::EAST,
+        Point::SOUTH_EAST,
+        Point::SOUTH,
+        Point::SOUTH_WEST,
+        Point::WEST,
+        Point::NORTH_WEST,
+    ];
+
+    /// Create a new `Point` at column `x` and row `y`
     #[inline]
     #[must_use]
     pub const fn new(x: Coord, y: Coord) -> Self {
         Point { x, y }
     }
+
+    /// Offsets to every cell at most `range` cells away horizontally and vertically, i.e. the
+    /// `(2 * range + 1)` square box around a cell without the cell itself. A `range` of `1`
+    /// gives the eight offsets in `DIRECTIONS`.
+    /// ```
+    /// use gridlife::Point;
+    /// assert_eq!(Point::neighbourhood(1).count(), 8);
+    /// assert_eq!(Point::neighbourhood(2).count(), 24);
+    /// ```
+    pub fn neighbourhood(range: u32) -> impl Iterator<Item = Point> {
+        let range = range as Coord;
+        (-range..=range)
+            .flat_map(move |y| (-range..=range).map(move |x| Point::new(x, y)))
+            .filter(|&p| p != Point::new(0, 0))
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+/// `Direction` names one of the eight neighbours of a cell
+pub enum Direction {
+    /// The cell above
+    North,
+    /// The cell above and to the right
+    NorthEast,
+    /// The cell to the right
+    East,
+    /// The cell below and to the right
+    SouthEast,
+    /// The cell below
+    South,
+    /// The cell below and to the left
+    SouthWest,
+    /// The cell to the left
+    West,
+    /// The cell above and to the left
+    NorthWest,
+}
+
+impl Direction {
+    /// All eight directions, clockwise from `North`
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The offset from a cell to its neighbour in this direction
+    /// ```
+    /// use gridlife::{Direction, Point};
+    /// assert_eq!(Direction::SouthWest.offset(), Point::SOUTH_WEST);
+    /// ```
+    #[must_use]
+    pub const fn offset(self) -> Point {
+        Point::DIRECTIONS[self as usize]
+    }
+}
+
+impl From<Direction> for Point {
+    fn from(dir: Direction) -> Self {
+        dir.offset()
+    }
 }
 
 #[derive(PartialEq, Clone, Copy, Debug)]
@@ -74,7 +284,51 @@ pub enum CellState {
     Alive(char),
     /// `Dead` with a `char` to be rendered
     Dead(char),
+    /// `Dying` with a `char` to be rendered and the number of generations left before it is
+    /// `Dead`. Only used by grids with more than two `states`, see `Grid::states`
+    Dying(char, u8),
+}
+impl CellState {
+    /// Whether the cell is `Alive`, irrespective of the glyph it is rendered with
+    pub fn is_alive(&self) -> bool {
+        matches!(self, CellState::Alive(_))
+    }
+
+    /// Whether the cell is `Dead`, irrespective of the glyph it is rendered with
+    pub fn is_dead(&self) -> bool {
+        matches!(self, CellState::Dead(_))
+    }
+
+    /// Whether the cell is `Dying`, irrespective of the glyph it is rendered with
+    pub fn is_dying(&self) -> bool {
+        matches!(self, CellState::Dying(..))
+    }
+
+    /// The glyph the cell is rendered with
+    pub fn glyph(&self) -> char {
+        match self {
+            CellState::Alive(c) | CellState::Dead(c) | CellState::Dying(c, _) => *c,
+        }
+    }
+
+    /// Number of terminal columns the glyph of the cell occupies.
+    /// Most characters are one column wide, but many emoji and CJK characters take two.
+    /// Control characters take none.
+    pub fn width(&self) -> usize {
+        self.glyph().width().unwrap_or(0)
+    }
+
+    /// Whether both cells are in the same state, ignoring the glyphs they are rendered with
+    fn same_state(&self, other: &CellState) -> bool {
+        match (self, other) {
+            (CellState::Alive(_), CellState::Alive(_)) => true,
+            (CellState::Dead(_), CellState::Dead(_)) => true,
+            (CellState::Dying(_, a), CellState::Dying(_, b)) => a == b,
+            _ => false,
+        }
+    }
 }
+
 impl Display for CellState {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
@@ -84,19 +338,66 @@ impl Display for CellState {
             CellState::Alive(c) => {
                 write!(f, "{c}")?;
             }
+            CellState::Dying(c, _) => {
+                write!(f, "{c}")?;
+            }
         }
         Ok(())
     }
 }
 
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `Motion` classifies how a pattern evolves over time, see `Grid::analyze_motion`
+pub enum Motion {
+    /// The pattern does not change between generations
+    Static,
+    /// The pattern returns to its original state in place after `period` generations
+    Oscillator {
+        /// Number of generations before the pattern repeats
+        period: usize,
+    },
+    /// The pattern returns to its original shape after `period` generations, translated by
+    /// `dx` and `dy` cells
+    Spaceship {
+        /// Number of generations before the shape repeats
+        period: usize,
+        /// Horizontal displacement per `period`
+        dx: i32,
+        /// Vertical displacement per `period`
+        dy: i32,
+    },
+    /// No repetition was found within the search limits
+    Unknown,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug)]
+/// `RunStats` summarises the population over a run of generations, see `Grid::run_stats`
+pub struct RunStats {
+    /// Smallest population seen
+    pub min: usize,
+    /// Largest population seen
+    pub max: usize,
+    /// Mean population across every generation seen, including the starting one
+    pub mean: f64,
+    /// Number of generations that were run
+    pub generations: usize,
+}
+
 #[derive(PartialEq, Clone, Copy, Debug)]
+/// Live and dead neighbours of a cell. Every cell has the same number of neighbours under every
+/// topology, with those beyond a bounded edge counted as the grid's `background`, or as alive
+/// for `Topology::BoundedAlive`, so `alive + dead` is always `8` for the default `range` of `1`.
 struct NeighbourState {
     dead: i32,
     alive: i32,
 }
-#[derive(Debug)]
+#[derive(Clone)]
 #[non_exhaustive]
 /// `Grid` holds the state for a Conways game of life
+///
+/// Glyphs that are a single terminal column wide, such as ASCII characters and box drawing
+/// characters like `█`, are always safe to use. Wide glyphs such as most emoji are padded when
+/// displayed so that columns still line up, see `Grid::cell_width`.
 pub struct Grid<T> {
     /// The `width` of the grid to be created
     pub width: usize,
@@ -104,24 +405,154 @@ pub struct Grid<T> {
     pub height: usize,
     /// The state of the grid in terms of what cells are alive and dead in automaton
     pub cells: Vec<T>,
-    /// What character glyph should be used to display a dead population
+    /// What character glyph should be used to display a dead population.
+    /// Changing this field only affects cells that die afterwards, use `Grid::set_dead_glyph`
+    /// to update existing cells too.
     pub dead_glyph: char,
-    /// What character glyph should be used to display an alive population
+    /// What character glyph should be used to display an alive population.
+    /// Changing this field only affects cells that are born afterwards, use
+    /// `Grid::set_alive_glyph` to update existing cells too.
     pub alive_glyph: char,
-    /// Population of the grid i.e number of alive cells
+    /// Population of the grid i.e number of alive cells.
+    /// Stepping counts it while computing the next generation rather than scanning the cells
+    /// again afterwards.
     pub population: usize,
+    /// The rules used to generate each generation, unless a custom `Rule` is set with
+    /// `Grid::set_rule`
+    pub rules: Ruleset,
+    /// Number of states a cell can be in, including alive and dead.
+    /// The default of `2` is the usual game of life. With more states, a live cell that does
+    /// not survive passes through `states - 2` generations of `Dying` before it is dead, as in
+    /// "Generations" rules such as Brian's Brain. Dying cells are not counted as live neighbours
+    /// and cannot be born into.
+    pub states: u8,
+    /// What character glyph should be used to display a dying population
+    pub dying_glyph: char,
+    /// How the edges of the grid behave when counting neighbours
+    pub topology: Topology,
+    /// How far away cells are counted as neighbours, see `Grid::range`
+    range: u32,
+    /// How live cells are coloured, see `ColorMode`
+    pub color_mode: ColorMode,
+    /// Maximum number of previous generations kept for `Grid::step_back`, defaulting to `0`.
+    /// Each kept generation costs a copy of `cells`, so the memory used grows by
+    /// `width * height` cells per generation up to this limit.
+    pub history_limit: usize,
+    /// Name, author and comments of the pattern the grid was loaded from, see `PatternMetadata`
+    pub metadata: PatternMetadata,
+    /// The quiescent state of the cells beyond a bounded edge, dead by default. A live
+    /// background suits rules where empty space comes alive, such as those with `B0`.
+    ///
+    /// Only edges that do not wrap under the `topology` have cells beyond them, so this is
+    /// unused on a `Toroidal` grid, and `Topology::BoundedAlive` always treats them as alive.
+    /// These cells are counted as neighbours but never towards this grid's `population`.
+    /// `with_ghost_border` copies them into the border of the grid it returns, where they are
+    /// ordinary cells and so are counted in that grid's `population`.
+    ///
+    /// `SparseGrid` has no edges and treats every cell it does not store as dead, so it has no
+    /// background.
+    pub background: CellState,
+    /// Number of generations each cell has been continuously alive for
+    age: Vec<u32>,
+    /// Previous generations of `cells`, most recent last
+    history: VecDeque<Vec<CellState>>,
+    /// Number of times each cell has changed state
+    activity: Vec<u32>,
+    /// Highest population reached by stepping since the last `reset_peak`
+    peak_population: usize,
+    /// Number of cells that changed state in the most recent step
+    last_changes: usize,
+    /// Number of generations stepped since the grid was made or last reset
+    generation: usize,
+    /// Neighbours of each cell on an edge, rebuilt when the size or topology changes
+    neighbour_table: NeighbourTable,
+    /// Custom transition function used instead of `rules` when stepping, see `Grid::set_rule`
+    rule: Option<Arc<dyn Rule>>,
 }
 
 impl<T> Grid<T> {
+    /// A `width` by `height` grid holding `cells`, with the default glyphs, rules and settings.
+    /// Every constructor starts from here so a new field only needs its default set once.
+    ///
+    /// Nothing per cell is tracked yet, so `age` and `activity` are left empty for
+    /// `from_cell_states` to size.
+    fn with_cells(width: usize, height: usize, cells: Vec<T>) -> Self {
+        Grid {
+            width,
+            height,
+            cells,
+            dead_glyph: ' ',
+            alive_glyph: 'X',
+            population: 0,
+            rules: Ruleset::default(),
+            states: 2,
+            dying_glyph: '+',
+            topology: Topology::default(),
+            range: 1,
+            color_mode: ColorMode::default(),
+            history_limit: 0,
+            metadata: PatternMetadata::default(),
+            background: CellState::Dead(' '),
+            age: Vec::new(),
+            history: VecDeque::new(),
+            activity: Vec::new(),
+            peak_population: 0,
+            last_changes: 0,
+            generation: 0,
+            neighbour_table: NeighbourTable::default(),
+            rule: None,
+        }
+    }
+
     fn contains(&self, p: &Point) -> bool {
         p.x >= 0 && (p.x as usize) < self.width && p.y >= 0 && (p.y as usize) < self.height
     }
 
     fn pos(&self, p: usize) -> Point {
-        Point::new((p % self.width) as i32, (p / self.width) as i32)
+        Point::new((p % self.width) as Coord, (p / self.width) as Coord)
     }
+    /// Index of the cell at `p`, which must be within the grid. Computed in `usize` so that
+    /// grids with more than `Coord::MAX` cells do not overflow.
     fn idx(&self, p: &Point) -> usize {
-        ((self.width as i32) * p.y + p.x) as usize
+        p.y as usize * self.width + p.x as usize
+    }
+
+    /// Get the cell at `point`, or `None` if it is outside the grid
+    /// ```
+    /// use gridlife::{Grid, Point};
+    /// let grid = Grid::new_empty(3, 3);
+    /// assert!(grid.get(Point::new(1, 1)).is_some());
+    /// assert!(grid.get(Point::new(3, 1)).is_none());
+    /// ```
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.try_get(point)
+    }
+
+    /// Get the cell at `point`, or a `GridError::OutOfBounds` error if it is outside the grid
+    /// ```
+    /// use gridlife::{Grid, GridError, Point};
+    /// let grid = Grid::new_empty(3, 3);
+    /// assert!(grid.cell(Point::new(2, 2)).unwrap().is_dead());
+    /// assert_eq!(grid.cell(Point::new(-1, 0)), Err(GridError::OutOfBounds { x: -1, y: 0 }));
+    /// ```
+    pub fn cell(&self, point: Point) -> Result<&T, GridError> {
+        self.try_get(point).ok_or(GridError::OutOfBounds {
+            x: point.x,
+            y: point.y,
+        })
+    }
+
+    /// Mutably get the cell at `point`, or a `GridError::OutOfBounds` error if it is outside
+    /// the grid. Like `IndexMut`, writing to the cell does not update `population` or cell ages.
+    pub fn cell_mut(&mut self, point: Point) -> Result<&mut T, GridError> {
+        if !self.contains(&point) {
+            return Err(GridError::OutOfBounds {
+                x: point.x,
+                y: point.y,
+            });
+        }
+        let idx = self.idx(&point);
+        Ok(&mut self.cells[idx])
     }
 
     fn try_get<U: AsRef<Point>>(&self, p: U) -> Option<&T> {
@@ -133,15 +564,70 @@ impl<T> Grid<T> {
     }
 }
 
+/// Indexes the cell at `pos`, panicking if it is outside the grid. Use `Grid::get` or
+/// `Grid::cell` to handle points that may be out of bounds.
 impl<T> Index<Point> for Grid<T> {
     type Output = T;
 
     #[inline]
     fn index(&self, pos: Point) -> &Self::Output {
+        assert!(
+            self.contains(&pos),
+            "cell ({}, {}) is outside a {}x{} grid",
+            pos.x,
+            pos.y,
+            self.width,
+            self.height
+        );
         &self.cells[self.idx(&pos)]
     }
 }
 
+/// Indexes the cell in column `x` and row `y`, panicking if it is outside the grid
+/// ```
+/// use gridlife::{CellState, Grid};
+/// let grid = Grid::from_ascii("..\n.O", 'O', '.');
+/// assert_eq!(grid[(1, 1)], CellState::Alive('O'));
+/// ```
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "cell ({x}, {y}) is outside a {}x{} grid",
+            self.width,
+            self.height
+        );
+        &self.cells[y * self.width + x]
+    }
+}
+
+/// Mutably indexes the cell in column `x` and row `y`, panicking if it is outside the grid.
+///
+/// Writing through the index does not update `population` or cell ages, use `Grid::set` to
+/// keep them in step.
+/// ```
+/// use gridlife::{CellState, Grid};
+/// let mut grid = Grid::new_empty(2, 2);
+/// grid[(0, 1)] = CellState::Alive('X');
+/// assert!(grid[(0, 1)].is_alive());
+/// assert_eq!(grid.population, 0);
+/// ```
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        assert!(
+            x < self.width && y < self.height,
+            "cell ({x}, {y}) is outside a {}x{} grid",
+            self.width,
+            self.height
+        );
+        &mut self.cells[y * self.width + x]
+    }
+}
+
 impl Grid<CellState> {
     /// Create a new `Grid` of a given `width` and `height`.
     /// It will default to `X` for alive glyph and ` ` for dead glyph
@@ -150,16 +636,238 @@ impl Grid<CellState> {
     /// let grid = Grid::new_empty(3, 3);
     /// ```
     pub fn new_empty(width: usize, height: usize) -> Self {
-        let size = width * height;
-        let cells: Vec<CellState> = (0..size).map(|_| CellState::Dead(' ')).collect();
+        Self::from_cell_states(width, height, vec![CellState::Dead(' '); width * height])
+    }
+
+    /// A `width` by `height` grid holding `cells` as for `with_cells`, with the `population`
+    /// counted and the ages and activity of every cell starting from `0`
+    fn from_cell_states(width: usize, height: usize, cells: Vec<CellState>) -> Self {
+        let size = cells.len();
         Grid {
-            width,
-            height,
-            cells,
-            ..Default::default()
+            population: cells.iter().filter(|c| c.is_alive()).count(),
+            age: vec![0; size],
+            activity: vec![0; size],
+            ..Self::with_cells(width, height, cells)
+        }
+    }
+
+    /// Create a new `Grid` of a given `width` and `height` where every cell is dead, which
+    /// follows `rules` rather than Conways game of life. The glyphs are the same as `new_empty`.
+    /// ```
+    /// use gridlife::{Grid, Ruleset};
+    /// let highlife = Ruleset::new(&[3, 6], &[2, 3]);
+    /// let grid = Grid::new_empty_with_rules(3, 3, highlife);
+    /// assert_eq!(grid.rules, highlife);
+    /// ```
+    pub fn new_empty_with_rules(width: usize, height: usize, rules: Ruleset) -> Self {
+        Grid {
+            rules,
+            ..Self::new_empty(width, height)
+        }
+    }
+
+    /// Create a new empty `Grid` of a given `width` and `height`, checking the dimensions are
+    /// usable rather than panicking later.
+    /// Fails if `width` is zero while `height` is not, if either is larger than `Coord::MAX`,
+    /// or if `width * height` overflows.
+    /// ```
+    /// use gridlife::{Grid, GridError};
+    /// assert!(Grid::try_new(3, 3).is_ok());
+    /// assert_eq!(Grid::try_new(0, 3).unwrap_err(), GridError::ZeroWidth { height: 3 });
+    /// ```
+    pub fn try_new(width: usize, height: usize) -> Result<Self, GridError> {
+        if width == 0 && height != 0 {
+            return Err(GridError::ZeroWidth { height });
+        }
+        let too_large = GridError::TooLarge { width, height };
+        width.checked_mul(height).ok_or(too_large)?;
+        let max = Coord::MAX as usize;
+        if width > max || height > max {
+            return Err(too_large);
+        }
+        Ok(Self::new_empty(width, height))
+    }
+
+    /// Create a new `Grid` of a given `width` and `height` where every cell is dead.
+    /// The glyphs can be overridden with `alive_glyph` and `dead_glyph`, which must differ and
+    /// must not be control characters such as `\n` that would garble the grid when printed.
+    ///
+    /// # Panics
+    /// Panics if the glyphs are the same or either is a control character.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::new_empty_custom_glyphs(3, 3, '#', '.');
+    /// assert_eq!(grid.to_string(), "...\n...\n...\n");
+    /// ```
+    pub fn new_empty_custom_glyphs(
+        width: usize,
+        height: usize,
+        alive_glyph: char,
+        dead_glyph: char,
+    ) -> Self {
+        if let Err(error) = check_glyphs(alive_glyph, dead_glyph) {
+            panic!("{error}");
+        }
+        Self::empty_with_glyphs(width, height, alive_glyph, dead_glyph)
+    }
+
+    /// Like `Grid::new_empty_custom_glyphs` but without checking the glyphs, for building grids
+    /// from the glyphs of another grid, which can be set to anything through its fields
+    fn empty_with_glyphs(width: usize, height: usize, alive_glyph: char, dead_glyph: char) -> Self {
+        Grid {
+            alive_glyph,
+            dead_glyph,
+            background: CellState::Dead(dead_glyph),
+            ..Self::from_cell_states(
+                width,
+                height,
+                vec![CellState::Dead(dead_glyph); width * height],
+            )
+        }
+    }
+
+    /// Create a new `Grid` from a multi-line string, where each line is a row of the grid.
+    /// Any char equal to `alive` is a live cell and everything else is dead.
+    /// The width is taken from the longest line, with shorter lines padded with dead cells.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii(".O.\n..O\nOOO", 'O', '.');
+    /// assert_eq!(grid.population, 5);
+    /// assert_eq!(grid.to_string(), ".O.\n..O\nOOO\n");
+    /// ```
+    pub fn from_ascii(s: &str, alive: char, dead: char) -> Self {
+        let rows: Vec<&str> = s.lines().collect();
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut grid = Self::empty_with_glyphs(width, rows.len(), alive, dead);
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == alive {
+                    grid.cells[y * width + x] = CellState::Alive(alive);
+                }
+            }
+        }
+        grid.population = grid.calculate_population();
+        grid
+    }
+
+    /// Create a new `Grid` from a multi-line string without needing to say which characters
+    /// are alive. Any of `X`, `O`, `#`, `*` or `1` is a live cell and every other character is
+    /// dead. The width is taken from the longest line and the height from the number of lines,
+    /// with shorter lines padded with dead cells. The grid uses the default glyphs of `X` for
+    /// alive and ` ` for dead.
+    ///
+    /// This is also how `Grid` implements `FromStr`, which never fails.
+    /// ```
+    /// use gridlife::{CellState, Grid};
+    /// let grid = Grid::from_str_auto("#*\n.1O");
+    /// assert_eq!((grid.width, grid.height, grid.population), (3, 2, 4));
+    /// let grid: Grid<CellState> = "X.\n.X".parse().unwrap();
+    /// assert_eq!(grid.population, 2);
+    /// ```
+    pub fn from_str_auto(s: &str) -> Self {
+        let rows: Vec<&str> = s.lines().collect();
+        let width = rows
+            .iter()
+            .map(|row| row.chars().count())
+            .max()
+            .unwrap_or(0);
+        let mut grid = Self::new_empty(width, rows.len());
+        for (y, row) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if AUTO_ALIVE.contains(&c) {
+                    grid.cells[y * width + x] = CellState::Alive(grid.alive_glyph);
+                }
+            }
+        }
+        grid.population = grid.calculate_population();
+        grid
+    }
+
+    /// Places the pattern in the middle of a new empty `Grid` of the given `width` and `height`,
+    /// keeping the glyphs and `metadata`.
+    /// If the pattern is larger than the new grid it is clipped evenly on each side.
+    /// ```
+    /// use gridlife::Grid;
+    /// let glider = Grid::from_ascii(".O.\n..O\nOOO", 'O', '.');
+    /// let grid = glider.centered_on(5, 5);
+    /// assert_eq!(grid.to_string(), ".....\n..O..\n...O.\n.OOO.\n.....\n");
+    /// ```
+    pub fn centered_on(self, width: usize, height: usize) -> Self {
+        let mut grid = Self::empty_with_glyphs(width, height, self.alive_glyph, self.dead_glyph);
+        let offset_x = (width as isize - self.width as isize) / 2;
+        let offset_y = (height as isize - self.height as isize) / 2;
+        for (idx, cell) in self.cells.iter().enumerate() {
+            if !cell.is_alive() {
+                continue;
+            }
+            let x = (idx % self.width) as isize + offset_x;
+            let y = (idx / self.width) as isize + offset_y;
+            if (0..width as isize).contains(&x) && (0..height as isize).contains(&y) {
+                grid.cells[y as usize * width + x as usize] = CellState::Alive(self.alive_glyph);
+            }
         }
+        grid.population = grid.calculate_population();
+        grid.metadata = self.metadata;
+        grid
     }
 
+    /// Generate a new `Grid` of a given `width` and `height` filled with a checkerboard of live
+    /// and dead cells, with the top left cell alive
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::new_checkerboard(4, 2);
+    /// assert_eq!(grid.to_string(), "X X \n X X\n");
+    /// assert_eq!(grid.population, 4);
+    /// ```
+    pub fn new_checkerboard(width: usize, height: usize) -> Self {
+        Self::new_pattern(width, height, |x, y| (x + y) % 2 == 0)
+    }
+    /// Generate a new `Grid` of a given `width` and `height` filled with vertical stripes
+    /// `period` cells wide, alternating between live and dead starting with a live stripe on
+    /// the left. A `period` of `0` is treated as `1`.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::new_stripes(6, 2, 2);
+    /// assert_eq!(grid.to_string(), "XX  XX\nXX  XX\n");
+    /// assert_eq!(grid.population, 8);
+    /// ```
+    pub fn new_stripes(width: usize, height: usize, period: usize) -> Self {
+        let period = period.max(1);
+        Self::new_pattern(width, height, |x, _| (x / period) % 2 == 0)
+    }
+    /// Generate a new `Grid` of a given `width` and `height` where only the cells at the `live`
+    /// `x`, `y` coordinates are alive. Coordinates outside the grid are ignored.
+    /// ```
+    /// use gridlife::Grid;
+    /// let glider = Grid::from_coords(3, 3, [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+    /// assert_eq!(glider.to_string(), " X \n  X\nXXX\n");
+    /// assert_eq!(glider.population, 5);
+    /// ```
+    pub fn from_coords(
+        width: usize,
+        height: usize,
+        live: impl IntoIterator<Item = (usize, usize)>,
+    ) -> Self {
+        let mut grid = Self::new_empty(width, height);
+        grid.set_many(live, true);
+        grid
+    }
+    /// A new `Grid` where the cell at `x`, `y` is alive if `alive(x, y)` is `true`
+    fn new_pattern(width: usize, height: usize, alive: impl Fn(usize, usize) -> bool) -> Self {
+        let mut grid = Self::new_empty(width, height);
+        for (idx, cell) in grid.cells.iter_mut().enumerate() {
+            if alive(idx % width, idx / width) {
+                *cell = CellState::Alive(grid.alive_glyph);
+            }
+        }
+        grid.population = grid.calculate_population();
+        grid
+    }
+    #[cfg(feature = "std")]
     fn generate_random_cells(size: usize, alive_glyph: char, dead_glyph: char) -> Vec<CellState> {
         (0..size)
             .map(|_| {
@@ -178,222 +886,2462 @@ impl Grid<CellState> {
     /// use gridlife::Grid;
     /// let grid = Grid::new_random(3, 3);
     /// ```
+    #[cfg(feature = "std")]
     pub fn new_random(width: usize, height: usize) -> Self {
-        let default = Self::default();
-        let cells: Vec<CellState> =
-            Self::generate_random_cells(width * height, default.alive_glyph, default.dead_glyph);
+        let cells = Self::generate_random_cells(width * height, 'X', ' ');
+        Self::from_cell_states(width, height, cells)
+    }
+
+    /// Generate a new `Grid` of a given `width` and `height` populated like `new_random`, which
+    /// follows `rules` rather than Conways game of life
+    /// ```
+    /// use gridlife::{Grid, Ruleset};
+    /// let seeds = Ruleset::new(&[2], &[]);
+    /// let grid = Grid::new_random_with_rules(3, 3, seeds);
+    /// assert_eq!(grid.rules, seeds);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new_random_with_rules(width: usize, height: usize, rules: Ruleset) -> Self {
         Grid {
-            width,
-            height,
-            cells,
-            ..default
+            rules,
+            ..Self::new_random(width, height)
         }
     }
-
+    /// Generate a new `Grid` of a given `width` and `height` populated like `new_random`, using
+    /// `seed` so the same seed always produces the same grid
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::new_random_seeded(8, 8, 42);
+    /// assert!(grid.same_pattern(&Grid::new_random_seeded(8, 8, 42)));
+    /// ```
+    pub fn new_random_seeded(width: usize, height: usize, seed: u64) -> Self {
+        let mut grid = Self::new_empty(width, height);
+        grid.randomize_seeded(0.5, seed);
+        grid
+    }
     /// Generate a new `Grid` of a given `width` and `height`
     /// It will be populated with a random distribution of Alive/Dead cells
-    /// The glyphs can be overriddne with `alive_glyph` and `dead_glyph`
+    /// The glyphs can be overriddne with `alive_glyph` and `dead_glyph`, which must differ and
+    /// must not be control characters
+    ///
+    /// # Panics
+    /// Panics if the glyphs are the same or either is a control character.
     /// ```
     /// use gridlife::Grid;
     /// let grid = Grid::new_random_custom_glyphs(3, 3, '1', '0');
     /// ```
+    #[cfg(feature = "std")]
     pub fn new_random_custom_glyphs(
         width: usize,
         height: usize,
         alive_glyph: char,
         dead_glyph: char,
     ) -> Self {
+        if let Err(error) = check_glyphs(alive_glyph, dead_glyph) {
+            panic!("{error}");
+        }
         let cells = Self::generate_random_cells(width * height, alive_glyph, dead_glyph);
-        let population = cells
-            .iter()
-            .filter(|&&c| c == CellState::Alive(alive_glyph))
-            .count();
         Grid {
-            width,
-            height,
-            cells,
             alive_glyph,
             dead_glyph,
-            population,
+            background: CellState::Dead(dead_glyph),
+            ..Self::from_cell_states(width, height, cells)
+        }
+    }
+    /// Generate a new `Grid` of a given `width` and `height` where only the cells within
+    /// `region`, given as `(x, y, width, height)`, are randomly alive with probability
+    /// `density` and everything else is dead. A region extending past the edges of the grid is
+    /// clamped to it.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::new_random_region(10, 10, (3, 3, 4, 4), 1.0);
+    /// assert_eq!(grid.population, 16);
+    /// assert!(grid.cells[0].is_dead());
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn new_random_region(
+        width: usize,
+        height: usize,
+        region: (usize, usize, usize, usize),
+        density: f64,
+    ) -> Self {
+        let mut grid = Self::new_empty(width, height);
+        let (x, y, region_width, region_height) = region;
+        let x_end = x.saturating_add(region_width).min(width);
+        let y_end = y.saturating_add(region_height).min(height);
+        let mut rng = fastrand::Rng::new();
+        for row in y..y_end {
+            for col in x..x_end {
+                if rng.f64() < density {
+                    grid.cells[row * width + col] = CellState::Alive(grid.alive_glyph);
+                }
+            }
         }
+        grid.population = grid.calculate_population();
+        grid
+    }
+    /// Computes the generation after `cells`, a `width` by `height` grid, under `rules` and
+    /// `topology` without needing a `Grid`. This is the same step as `update_states` on a grid
+    /// made with `Grid::new_empty`, so live cells in the result use the glyph `X` and cells that
+    /// die use ` `, while cells that stay dead are unchanged.
+    ///
+    /// # Panics
+    /// Panics if `cells` does not hold `width * height` cells.
+    /// ```
+    /// use gridlife::{Grid, Ruleset, Topology};
+    /// let blinker = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+    /// let next = Grid::next_generation(&blinker.cells, 3, 3, Ruleset::default(), Topology::Bounded);
+    /// assert!(next[1].is_alive() && next[4].is_alive() && next[7].is_alive());
+    /// assert!(next[3].is_dead());
+    /// ```
+    pub fn next_generation(
+        cells: &[CellState],
+        width: usize,
+        height: usize,
+        rules: Ruleset,
+        topology: Topology,
+    ) -> Vec<CellState> {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells"
+        );
+        let mut grid = Grid {
+            rules,
+            topology,
+            ..Self::from_cell_states(width, height, cells.to_vec())
+        };
+        grid.next_cells().0
     }
     /// Re-generates the state of the `Grid` `cells` based on the rules of Conways game of life
     pub fn update_states(&mut self) -> &[CellState] {
-        let mut new_grid: Vec<CellState> = Vec::new();
-        for (idx, &cell) in self.cells.iter().enumerate() {
-            let state = self.get_neighbours_state(self.pos(idx));
-            let cellstate = self.get_cell_state(&cell, state);
-            new_grid.push(cellstate);
-        }
-        self.cells = new_grid;
-        self.population = self.calculate_population();
+        let (new_grid, population) = self.next_cells();
+        self.apply_next(new_grid, population);
         &self.cells
     }
-    fn calculate_population(&self) -> usize {
-        self.cells
+    /// Re-generates the state of the `Grid` like `update_states`, returning the `x` and `y`
+    /// coordinates and new state of every cell that changed state.
+    /// ```
+    /// use gridlife::{CellState, Grid};
+    /// let mut grid = Grid::from_ascii("OO", 'O', '.');
+    /// let changes = grid.update_states_delta();
+    /// assert_eq!(changes, vec![(0, 0, CellState::Dead('.')), (1, 0, CellState::Dead('.'))]);
+    /// ```
+    pub fn update_states_delta(&mut self) -> Vec<(usize, usize, CellState)> {
+        let (new_grid, population) = self.next_cells();
+        let changes = self
+            .cells
             .iter()
-            .filter(|&&c| c == CellState::Alive(self.alive_glyph))
-            .count()
+            .zip(&new_grid)
+            .enumerate()
+            .filter(|(_, (old, new))| !old.same_state(new))
+            .map(|(idx, (_, &new))| (idx % self.width, idx / self.width, new))
+            .collect();
+        self.apply_next(new_grid, population);
+        changes
     }
-    /// Gets the new state of the current cell based on the following rules:
-    /// - Any live cell with 0 or 1 live neighbors becomes dead, because of underpopulation
-    /// - Any live cell with 2 or 3 live neighbors stays alive, because its neighborhood is just right
-    /// - Any live cell with more than 3 live neighbors becomes dead, because of overpopulation
-    /// - Any dead cell with exactly 3 live neighbors becomes alive, by reproduction
-    fn get_cell_state(&self, cell: &CellState, state: NeighbourState) -> CellState {
-        match (&cell, state.alive) {
-            (CellState::Alive(_), 0..=1) => CellState::Dead(self.dead_glyph),
-            (CellState::Alive(_), 2..=3) => CellState::Alive(self.alive_glyph),
-            (CellState::Alive(_), 4..=8) => CellState::Dead(self.dead_glyph),
-            (CellState::Dead(_), 3) => CellState::Alive(self.alive_glyph),
-            (_, _) => *cell,
+    /// Re-generates the state of only the cells within `rect`, given as `(x, y, width, height)`,
+    /// and a border around it as wide as the `range`, leaving every other cell as it is. A
+    /// rectangle extending past the edges of the grid is clamped to it.
+    ///
+    /// This only matches `update_states` when nothing outside the rectangle and its border would
+    /// change, such as when editing a small area of an otherwise still grid. Anything outside
+    /// is frozen, so a pattern crossing the border of the rectangle will be cut apart.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii(".....O\nOOO..O\n.....O", 'O', '.');
+    /// grid.update_region((0, 0, 2, 3));
+    /// assert_eq!(grid.to_string(), ".O...O\n.O...O\n.O...O\n");
+    /// ```
+    pub fn update_region(&mut self, rect: (usize, usize, usize, usize)) -> &[CellState] {
+        let (x, y, width, height) = rect;
+        let border = self.range as usize;
+        let x_end = x
+            .saturating_add(width)
+            .saturating_add(border)
+            .min(self.width);
+        let y_end = y
+            .saturating_add(height)
+            .saturating_add(border)
+            .min(self.height);
+        let mut new_grid = self.cells.clone();
+        for row in y.saturating_sub(border)..y_end {
+            for col in x.saturating_sub(border)..x_end {
+                let idx = row * self.width + col;
+                let state = self.get_neighbours_state(self.pos(idx));
+                new_grid[idx] = self.get_cell_state(&self.cells[idx], state);
+            }
         }
+        self.apply_colors(&mut new_grid);
+        // Recounted rather than adjusted, as `population` may be stale after editing `cells`
+        let population = new_grid.iter().filter(|c| c.is_alive()).count();
+        self.apply_next(new_grid, population);
+        &self.cells
     }
-    fn get_neighbours_state(&self, point: Point) -> NeighbourState {
-        let mut alive = 0;
-        let mut dead = 0;
-        for neighbour in ORTHO_PLUS_DIR
-            .into_iter()
-            .map(move |d| point + d)
-            .map(|p| self.try_get(p))
-        {
-            match neighbour {
-                Some(c) => match c {
-                    CellState::Alive(_) => alive += 1,
-                    CellState::Dead(_) => dead += 1,
-                },
-                None => {
-                    // Neighbour is outside the bounds of the grid
-                    continue;
-                }
+    /// Computes the next generation along with its population, which is counted here so that
+    /// stepping does not need a second pass over the cells
+    fn next_cells(&mut self) -> (Vec<CellState>, usize) {
+        self.neighbour_table
+            .refresh(self.width, self.height, self.topology);
+        let mut new_grid: Vec<CellState> = Vec::with_capacity(self.cells.len());
+        let mut population = 0;
+        let outside_alive = self.outside_cell().is_alive();
+        // Edge cells are visited in the same order as the table lists them
+        let mut edge = 0;
+        for y in 0..self.height {
+            let interior_row = y > 0 && y + 1 < self.height;
+            for x in 0..self.width {
+                let idx = y * self.width + x;
+                let state = if self.range != 1 {
+                    self.get_neighbours_state(self.pos(idx))
+                } else if interior_row && x > 0 && x + 1 < self.width {
+                    self.get_interior_neighbours_state(idx)
+                } else {
+                    edge += 1;
+                    self.neighbour_table
+                        .state(edge - 1, &self.cells, outside_alive)
+                };
+                let next = self.get_cell_state(&self.cells[idx], state);
+                population += usize::from(next.is_alive());
+                new_grid.push(next);
             }
         }
-        NeighbourState { alive, dead }
+        self.apply_colors(&mut new_grid);
+        (new_grid, population)
     }
-}
-
-impl Default for Grid<CellState> {
-    fn default() -> Self {
-        let size = 10 * 10;
-        let cells: Vec<CellState> = (0..size).map(|_| CellState::Dead(' ')).collect();
-        Grid {
-            width: 10,
-            height: 10,
-            cells,
-            alive_glyph: 'X',
-            dead_glyph: ' ',
-            population: 0,
+    fn apply_next(&mut self, new_grid: Vec<CellState>, population: usize) {
+        self.update_ages(&new_grid);
+        self.update_activity(&new_grid);
+        let previous = core::mem::replace(&mut self.cells, new_grid);
+        if self.history_limit > 0 {
+            while self.history.len() >= self.history_limit {
+                self.history.pop_front();
+            }
+            self.history.push_back(previous);
         }
+        self.peak_population = self.peak_population.max(self.population).max(population);
+        self.population = population;
+        self.generation += 1;
     }
-}
-
+    /// Restores the generation before the most recent one, returning `false` if no previous
+    /// generation was kept. Up to `history_limit` generations can be stepped back through.
+    ///
+    /// Ages are not stored in the history, so a cell that was alive in both generations has its
+    /// age reduced by one and a cell that is revived starts again from `0`.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+    /// grid.history_limit = 10;
+    /// grid.update_states();
+    /// assert_eq!(grid.to_string(), ".O.\n.O.\n.O.\n");
+    /// assert!(grid.step_back());
+    /// assert_eq!(grid.to_string(), "...\nOOO\n...\n");
+    /// assert!(!grid.step_back());
+    /// ```
+    pub fn step_back(&mut self) -> bool {
+        let previous = match self.history.pop_back() {
+            Some(previous) => previous,
+            None => return false,
+        };
+        for (age, (now, before)) in self.age.iter_mut().zip(self.cells.iter().zip(&previous)) {
+            *age = if now.is_alive() && before.is_alive() {
+                age.saturating_sub(1)
+            } else {
+                0
+            };
+        }
+        self.cells = previous;
+        self.population = self.calculate_population();
+        self.generation = self.generation.saturating_sub(1);
+        true
+    }
+    /// Sets the cell at `point` to be alive or dead using the grid's glyphs, keeping the
+    /// `population` up to date. Returns the previous state of the cell, or `None` if `point` is
+    /// outside the grid.
+    /// ```
+    /// use gridlife::{CellState, Grid, Point};
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// assert_eq!(grid.set(Point::new(1, 1), true), Some(CellState::Dead(' ')));
+    /// assert_eq!(grid.population, 1);
+    /// ```
+    pub fn set(&mut self, point: Point, alive: bool) -> Option<CellState> {
+        if !self.contains(&point) {
+            return None;
+        }
+        let idx = self.idx(&point);
+        let previous = self.cells[idx];
+        match (previous.is_alive(), alive) {
+            (false, true) => self.population += 1,
+            (true, false) => self.population -= 1,
+            _ => {}
+        }
+        if !alive {
+            self.age[idx] = 0;
+        }
+        self.cells[idx] = if alive {
+            CellState::Alive(self.alive_glyph)
+        } else {
+            CellState::Dead(self.dead_glyph)
+        };
+        Some(previous)
+    }
+    /// Sets every cell at the given `x`, `y` coordinates alive like `set`, skipping any outside
+    /// the grid. The `population` is counted once at the end rather than for each cell.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// grid.set_alive_many(&[(1, 0), (2, 1), (0, 2), (1, 2), (2, 2), (5, 5)]);
+    /// assert_eq!(grid.to_string(), " X \n  X\nXXX\n");
+    /// assert_eq!(grid.population, 5);
+    /// ```
+    pub fn set_alive_many(&mut self, coords: &[(usize, usize)]) {
+        self.set_many(coords.iter().copied(), true);
+    }
+    /// Sets every cell at the given `x`, `y` coordinates dead like `set`, skipping any outside
+    /// the grid. The `population` is counted once at the end rather than for each cell.
+    pub fn set_dead_many(&mut self, coords: &[(usize, usize)]) {
+        self.set_many(coords.iter().copied(), false);
+    }
+    fn set_many(&mut self, coords: impl IntoIterator<Item = (usize, usize)>, alive: bool) {
+        for (x, y) in coords {
+            if x >= self.width || y >= self.height {
+                continue;
+            }
+            let idx = y * self.width + x;
+            self.cells[idx] = if alive {
+                CellState::Alive(self.alive_glyph)
+            } else {
+                self.age[idx] = 0;
+                CellState::Dead(self.dead_glyph)
+            };
+        }
+        self.population = self.calculate_population();
+    }
+    /// Flips the cell in column `x` and row `y` between alive and dead using the grid's glyphs,
+    /// keeping the `population` up to date. A dying cell counts as dead and is revived.
+    /// Returns the new state of the cell, or `None` if it is outside the grid.
+    /// ```
+    /// use gridlife::{CellState, Grid};
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// assert_eq!(grid.toggle(1, 1), Some(CellState::Alive('X')));
+    /// assert_eq!(grid.population, 1);
+    /// assert_eq!(grid.toggle(3, 1), None);
+    /// ```
+    pub fn toggle(&mut self, x: usize, y: usize) -> Option<CellState> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let point = Point::new(x as Coord, y as Coord);
+        let alive = !self[point].is_alive();
+        self.set(point, alive)?;
+        Some(self[point])
+    }
+    /// Changes the `alive_glyph`, rewriting every live cell and a live `background` that used
+    /// the previous glyph.
+    /// Live cells with other glyphs, such as other teams under a `ColorMode`, are left as is.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("O.\n.O", 'O', '.');
+    /// grid.set_alive_glyph('#');
+    /// assert_eq!(grid.to_string(), "#.\n.#\n");
+    /// ```
+    pub fn set_alive_glyph(&mut self, glyph: char) {
+        let previous = core::mem::replace(&mut self.alive_glyph, glyph);
+        for cell in self.cells.iter_mut() {
+            if *cell == CellState::Alive(previous) {
+                *cell = CellState::Alive(glyph);
+            }
+        }
+        if self.background == CellState::Alive(previous) {
+            self.background = CellState::Alive(glyph);
+        }
+        self.population = self.calculate_population();
+    }
+    /// Changes the `dead_glyph`, rewriting every dead cell and a dead `background`
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("O.\n.O", 'O', '.');
+    /// grid.set_dead_glyph(' ');
+    /// assert_eq!(grid.to_string(), "O \n O\n");
+    /// ```
+    pub fn set_dead_glyph(&mut self, glyph: char) {
+        self.dead_glyph = glyph;
+        for cell in self.cells.iter_mut() {
+            if cell.is_dead() {
+                *cell = CellState::Dead(glyph);
+            }
+        }
+        if self.background.is_dead() {
+            self.background = CellState::Dead(glyph);
+        }
+        self.population = self.calculate_population();
+    }
+    /// Whether no cell on the grid is alive
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("O.", 'O', '.');
+    /// assert!(!grid.is_empty());
+    /// grid.update_states();
+    /// assert!(grid.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.population == 0
+    }
+    /// Whether every cell on the grid is alive
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii("OO\nOO", 'O', '.');
+    /// assert!(grid.is_full());
+    /// ```
+    pub fn is_full(&self) -> bool {
+        self.population == self.width * self.height
+    }
+    /// Sets every cell to dead in place, keeping the dimensions, glyphs and rules. The
+    /// `generation` count starts again from `0`, and the history, activity and ages are
+    /// forgotten as for a new grid.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_random(3, 3);
+    /// grid.clear();
+    /// assert_eq!(grid.population, 0);
+    /// ```
+    pub fn clear(&mut self) {
+        self.cells.fill(CellState::Dead(self.dead_glyph));
+        self.reset_tracking();
+        self.population = 0;
+        self.generation = 0;
+    }
+    /// Forgets the ages, history, activity and changes tracked while stepping, for when the
+    /// cells are replaced wholesale
+    fn reset_tracking(&mut self) {
+        self.age.fill(0);
+        self.history.clear();
+        self.reset_activity();
+        self.last_changes = 0;
+    }
+    /// Returns the number of generations stepped since the grid was made, `clear`ed or
+    /// `reset_generation` was called. `step_back` counts back down.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// grid.update_states();
+    /// grid.update_states();
+    /// assert_eq!(grid.generation(), 2);
+    /// ```
+    pub fn generation(&self) -> usize {
+        self.generation
+    }
+    /// Whether the current `generation` is odd, flipping with every step. Algorithms that
+    /// alternate between two scans or buffers can use this to pick the one to use.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// assert!(!grid.generation_parity());
+    /// grid.update_states();
+    /// assert!(grid.generation_parity());
+    /// ```
+    pub fn generation_parity(&self) -> bool {
+        self.generation % 2 == 1
+    }
+    /// Starts counting generations again from `0`, which also makes `generation_parity` even
+    pub fn reset_generation(&mut self) {
+        self.generation = 0;
+    }
+    /// Steps the grid with `rule` rather than `rules`, so any automaton that depends only on a
+    /// cell and its number of live neighbours can be run. The rule decides how long cells stay
+    /// `Dying` for, so `states` is ignored, and the glyphs of the states it returns are
+    /// replaced with the grid's own.
+    /// ```
+    /// use gridlife::{CellState, Grid, Rule};
+    /// // Fredkin's replicator: a cell is alive when an odd number of its neighbours are
+    /// #[derive(Debug)]
+    /// struct Parity;
+    /// impl Rule for Parity {
+    ///     fn next(&self, _current: CellState, live_neighbours: u32) -> CellState {
+    ///         if live_neighbours % 2 == 1 {
+    ///             CellState::Alive('O')
+    ///         } else {
+    ///             CellState::Dead('.')
+    ///         }
+    ///     }
+    /// }
+    /// let mut grid = Grid::from_ascii(".....\n.....\n..O..\n.....\n.....", 'O', '.');
+    /// grid.set_rule(Box::new(Parity));
+    /// grid.update_states();
+    /// assert_eq!(grid.to_string(), ".....\n.OOO.\n.O.O.\n.OOO.\n.....\n");
+    /// ```
+    pub fn set_rule(&mut self, rule: Box<dyn Rule>) {
+        self.rule = Some(Arc::from(rule));
+    }
+    /// Goes back to stepping the grid with `rules` after `set_rule`
+    pub fn clear_rule(&mut self) {
+        self.rule = None;
+    }
+    /// How far away cells are counted as neighbours, defaulting to `1` for the usual eight
+    /// adjacent cells. With a `range` of `r` every cell in the `(2r + 1)` square box around a
+    /// cell is a neighbour, as in "Larger than Life" rules, so a `range` of `2` gives up to 24
+    /// live neighbours.
+    pub fn range(&self) -> u32 {
+        self.range
+    }
+    /// Sets how far away cells are counted as neighbours, see `Grid::range`
+    ///
+    /// `Ruleset` counts up to 127 live neighbours, so the `range` must be between `1` and `5`
+    /// or a `GridError::InvalidRange` error is returned. Grids with a `range` other than `1`
+    /// step more slowly as they cannot use the precomputed neighbours.
+    /// ```
+    /// use gridlife::{Grid, GridError};
+    /// let mut grid = Grid::new_empty(5, 5);
+    /// assert_eq!(grid.set_range(2), Ok(()));
+    /// assert_eq!(grid.range(), 2);
+    /// assert_eq!(grid.set_range(6), Err(GridError::InvalidRange { range: 6 }));
+    /// assert_eq!(grid.range(), 2);
+    /// ```
+    pub fn set_range(&mut self, range: u32) -> Result<(), GridError> {
+        check_range(range)?;
+        self.range = range;
+        Ok(())
+    }
+    /// Flips every cell in place, so live cells become dead and every other cell, including
+    /// dying ones, becomes alive
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("O.\n..", 'O', '.');
+    /// grid.invert();
+    /// assert_eq!(grid.to_string(), ".O\nOO\n");
+    /// assert_eq!(grid.population, 3);
+    /// ```
+    pub fn invert(&mut self) {
+        for cell in self.cells.iter_mut() {
+            *cell = if cell.is_alive() {
+                CellState::Dead(self.dead_glyph)
+            } else {
+                CellState::Alive(self.alive_glyph)
+            };
+        }
+        self.age.fill(0);
+        self.population = self.calculate_population();
+    }
+    /// Re-fills the grid in place with a random distribution of Alive/Dead cells, where each
+    /// cell is alive with probability `density`. The history, activity and ages are forgotten
+    /// as for a new grid.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_empty(10, 10);
+    /// grid.randomize(1.0);
+    /// assert_eq!(grid.population, 100);
+    /// ```
+    #[cfg(feature = "std")]
+    pub fn randomize(&mut self, density: f64) {
+        self.randomize_with(density, &mut fastrand::Rng::new());
+    }
+    /// Re-fills the grid in place like `randomize`, using `seed` so the same seed always
+    /// produces the same grid
+    pub fn randomize_seeded(&mut self, density: f64, seed: u64) {
+        self.randomize_with(density, &mut fastrand::Rng::with_seed(seed));
+    }
+    fn randomize_with(&mut self, density: f64, rng: &mut fastrand::Rng) {
+        for cell in self.cells.iter_mut() {
+            *cell = if rng.f64() < density {
+                CellState::Alive(self.alive_glyph)
+            } else {
+                CellState::Dead(self.dead_glyph)
+            };
+        }
+        self.reset_tracking();
+        self.population = self.calculate_population();
+    }
+    /// Returns the neighbour of the cell at `x`, `y` in direction `dir`, wrapping according to
+    /// the grid's `topology`. Returns `None` if `x`, `y` is outside the grid or the neighbour
+    /// lies beyond a bounded edge.
+    /// ```
+    /// use gridlife::{Direction, Grid, Topology};
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// grid.set((1, 0).into(), true);
+    /// assert!(grid.neighbour(1, 1, Direction::North).unwrap().is_alive());
+    /// assert_eq!(grid.neighbour(1, 2, Direction::South), None);
+    /// grid.topology = Topology::Toroidal;
+    /// assert!(grid.neighbour(1, 2, Direction::South).unwrap().is_alive());
+    /// ```
+    pub fn neighbour(&self, x: usize, y: usize, dir: Direction) -> Option<&CellState> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let point = Point::new(x as Coord, y as Coord) + dir.offset();
+        let resolved = self.topology.resolve(point, self.width, self.height)?;
+        self.get(resolved)
+    }
+    /// Whether `other` has the same dimensions and the same pattern of cell states as this grid,
+    /// ignoring the glyphs either grid renders with.
+    ///
+    /// Grids with different populations are rejected without comparing their cells, so both
+    /// `population` fields must be up to date, as they are unless `cells` is edited directly.
+    /// ```
+    /// use gridlife::Grid;
+    /// let a = Grid::from_ascii("X.\n.X", 'X', '.');
+    /// let b = Grid::from_ascii("#_\n_#", '#', '_');
+    /// assert!(a.same_pattern(&b));
+    /// ```
+    pub fn same_pattern(&self, other: &Grid<CellState>) -> bool {
+        self.width == other.width
+            && self.height == other.height
+            && self.population == other.population
+            && self
+                .cells
+                .iter()
+                .zip(other.cells.iter())
+                .all(|(a, b)| a.same_state(b))
+    }
+    /// Whether the live cells of the grid form the same shape as the live cells of `pattern`,
+    /// a multi-line string where `alive` marks a live cell and any other character is dead.
+    ///
+    /// Both are compared by the smallest box containing their live cells, so the dead space
+    /// around the pattern and where it sits on the grid do not matter, and neither do glyphs.
+    /// A pattern with no live cells matches a grid with no live cells.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii(".....\n.....\n.OOO.\n.....\n.....", 'O', '.');
+    /// grid.update_states();
+    /// assert!(grid.matches_ascii("O\nO\nO", 'O'));
+    /// assert!(grid.matches_ascii("..#..\n..#..\n..#..", '#'));
+    /// assert!(!grid.matches_ascii("OOO", 'O'));
+    /// ```
+    pub fn matches_ascii(&self, pattern: &str, alive: char) -> bool {
+        let pattern = Self::from_ascii(pattern, alive, ' ');
+        let shape = |grid: &Self| -> Vec<Point> {
+            let min = grid.live_bounds().map_or(Point::new(0, 0), |(min, _)| min);
+            grid.live_points().into_iter().map(|p| p - min).collect()
+        };
+        shape(self) == shape(&pattern)
+    }
+    /// Kills every live cell that has no live neighbours.
+    /// Under the rules of Conways game of life these cells are guaranteed to die on the next
+    /// update, so removing them early is a cheap way to clean up noise.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("O....\n.....\n..OO.\n..OO.", 'O', '.');
+    /// grid.remove_isolated();
+    /// assert_eq!(grid.to_string(), ".....\n.....\n..OO.\n..OO.\n");
+    /// assert_eq!(grid.population, 4);
+    /// ```
+    pub fn remove_isolated(&mut self) {
+        let isolated: Vec<usize> = (0..self.cells.len())
+            .filter(|&idx| {
+                self.cells[idx].is_alive() && self.get_neighbours_state(self.pos(idx)).alive == 0
+            })
+            .collect();
+        for idx in isolated {
+            self.cells[idx] = CellState::Dead(self.dead_glyph);
+            self.age[idx] = 0;
+        }
+        self.population = self.calculate_population();
+    }
+    /// Number of generations each cell has been continuously alive for, in row-major order.
+    /// A newly born cell has an age of `0`, as does every dead cell.
+    pub fn ages(&self) -> &[u32] {
+        &self.age
+    }
+    fn update_ages(&mut self, next: &[CellState]) {
+        self.age.resize(next.len(), 0);
+        for ((age, prev), next) in self.age.iter_mut().zip(&self.cells).zip(next) {
+            *age = match (prev, next) {
+                (CellState::Alive(_), CellState::Alive(_)) => *age + 1,
+                _ => 0,
+            };
+        }
+    }
+    /// Returns the number of live neighbours of each cell under the grid's `topology` and
+    /// `range`, in the same order as `cells`. These are the counts the rules are applied to when
+    /// stepping.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+    /// assert_eq!(grid.neighbour_counts(), vec![2, 3, 2, 1, 2, 1, 2, 3, 2]);
+    /// ```
+    pub fn neighbour_counts(&self) -> Vec<u32> {
+        (0..self.cells.len())
+            .map(|idx| {
+                let (x, y) = (idx % self.width, idx / self.width);
+                let interior = x > 0 && y > 0 && x + 1 < self.width && y + 1 < self.height;
+                let state = if interior && self.range == 1 {
+                    self.get_interior_neighbours_state(idx)
+                } else {
+                    self.get_neighbours_state(self.pos(idx))
+                };
+                state.alive as u32
+            })
+            .collect()
+    }
+    /// Returns how many times each cell has changed state, in the same order as `cells`.
+    /// Counts accumulate across every generation until `reset_activity` is called.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+    /// grid.update_states();
+    /// grid.update_states();
+    /// assert_eq!(grid.activity_map(), &[0, 2, 0, 2, 0, 2, 0, 2, 0]);
+    /// ```
+    pub fn activity_map(&self) -> &[u32] {
+        &self.activity
+    }
+    /// Sets the activity count of every cell back to `0`
+    pub fn reset_activity(&mut self) {
+        self.activity.clear();
+        self.activity.resize(self.cells.len(), 0);
+    }
+    /// Returns the highest population the grid has had since it was made or `reset_peak` was
+    /// last called, counting the current generation
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii(".O..\n.OO.\n....", 'O', '.');
+    /// grid.update_states();
+    /// grid.update_states();
+    /// assert_eq!((grid.population, grid.peak_population()), (4, 4));
+    /// grid.clear();
+    /// assert_eq!(grid.peak_population(), 4);
+    /// grid.reset_peak();
+    /// assert_eq!(grid.peak_population(), 0);
+    /// ```
+    pub fn peak_population(&self) -> usize {
+        self.peak_population.max(self.population)
+    }
+    /// Forgets the highest population reached, so it is tracked again from the current
+    /// generation
+    pub fn reset_peak(&mut self) {
+        self.peak_population = 0;
+    }
+    /// Returns the fraction of cells that changed state in the most recent step, from `0.0`
+    /// for a grid that has settled or not been stepped yet to `1.0` if every cell changed.
+    /// Frozen grids stay at `0.0`, oscillators hover at a steady rate and chaotic grids change
+    /// the most.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+    /// assert_eq!(grid.change_rate(), 0.0);
+    /// grid.update_states();
+    /// assert_eq!(grid.change_rate(), 4.0 / 9.0);
+    /// ```
+    pub fn change_rate(&self) -> f64 {
+        if self.cells.is_empty() {
+            return 0.0;
+        }
+        self.last_changes as f64 / self.cells.len() as f64
+    }
+    fn update_activity(&mut self, next: &[CellState]) {
+        self.activity.resize(next.len(), 0);
+        self.last_changes = 0;
+        for ((count, prev), next) in self.activity.iter_mut().zip(&self.cells).zip(next) {
+            if !prev.same_state(next) {
+                *count += 1;
+                self.last_changes += 1;
+            }
+        }
+    }
+    /// Renders the grid like `Display`, but picks the glyph for each live cell from `ramp`
+    /// based on its age, so the oldest cells use the last glyph of the `ramp`.
+    /// Ages beyond the end of the `ramp` are clamped to its last glyph.
+    /// An empty `ramp` falls back to the `alive_glyph`.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("OO....\nOO..O.\n....O.\n....O.", 'O', '.');
+    /// grid.update_states();
+    /// // The block and the middle of the blinker survived, the blinker's ends are newborn
+    /// assert_eq!(
+    ///     grid.to_heat_string(&['-', 'o', 'O']),
+    ///     "oo....\noo....\n...-o-\n......\n"
+    /// );
+    /// ```
+    pub fn to_heat_string(&self, ramp: &[char]) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        for row in 0..self.height {
+            for idx in row * self.width..(row + 1) * self.width {
+                let glyph = match self.cells[idx] {
+                    CellState::Alive(_) => ramp
+                        .get(self.age[idx] as usize)
+                        .or_else(|| ramp.last())
+                        .copied()
+                        .unwrap_or(self.alive_glyph),
+                    CellState::Dying(..) => self.dying_glyph,
+                    CellState::Dead(_) => self.dead_glyph,
+                };
+                out.push(glyph);
+            }
+            out.push('\n');
+        }
+        out
+    }
+    /// Records `generations` generations of the grid into a single flat buffer of shape
+    /// `[generations, height, width]`, returned alongside that shape.
+    /// Each cell is `1` when alive and `0` when dead. The first slice is the current state of
+    /// the grid, and the grid is left at the last recorded generation.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_random(4, 3);
+    /// let (buffer, shape) = grid.evolve_to_tensor(5);
+    /// assert_eq!(shape, [5, 3, 4]);
+    /// assert_eq!(buffer.len(), 5 * 3 * 4);
+    /// ```
+    pub fn evolve_to_tensor(&mut self, generations: usize) -> (Vec<u8>, [usize; 3]) {
+        let mut buffer = Vec::with_capacity(generations * self.cells.len());
+        for generation in 0..generations {
+            if generation > 0 {
+                self.update_states();
+            }
+            buffer.extend(self.cells.iter().map(|c| u8::from(c.is_alive())));
+        }
+        (buffer, [generations, self.height, self.width])
+    }
+    /// Classifies the pattern on the grid by stepping it up to `max_period` generations and
+    /// comparing each generation to the starting pattern, shifted by up to `max_shift` cells in
+    /// each direction.
+    ///
+    /// The generations are stepped on a copy, so the grid is left as it was. As the grid is
+    /// bounded, spaceships need enough room to travel for a full period without reaching an
+    /// edge.
+    /// ```
+    /// use gridlife::{CellState, Grid, Motion};
+    /// let mut grid = Grid::new_empty(4, 4);
+    /// for idx in [5, 6, 9, 10] {
+    ///     grid.cells[idx] = CellState::Alive('X');
+    /// }
+    /// assert_eq!(grid.analyze_motion(4, 2), Motion::Static);
+    /// ```
+    pub fn analyze_motion(&self, max_period: usize, max_shift: usize) -> Motion {
+        // Step a copy, so nothing tracked while stepping such as the history or peak population
+        // needs putting back afterwards
+        let mut probe = self.clone();
+        probe.history_limit = 0;
+        let start = probe.live_points();
+        let max_shift = max_shift as i32;
+
+        let mut motion = Motion::Unknown;
+        for period in 1..=max_period {
+            probe.update_states();
+            let current = probe.live_points();
+            if current.len() != start.len() {
+                continue;
+            }
+            let (dx, dy) = match (start.first(), current.first()) {
+                (Some(a), Some(b)) => (b.x - a.x, b.y - a.y),
+                _ => (0, 0),
+            };
+            if dx.abs() > max_shift || dy.abs() > max_shift {
+                continue;
+            }
+            let shift = Point::new(dx, dy);
+            if start.iter().zip(&current).all(|(&a, &b)| a + shift == b) {
+                motion = match (period, dx, dy) {
+                    (1, 0, 0) => Motion::Static,
+                    (_, 0, 0) => Motion::Oscillator { period },
+                    _ => Motion::Spaceship { period, dx, dy },
+                };
+                break;
+            }
+        }
+
+        motion
+    }
+    /// Iterates over the `x` and `y` coordinates of every live cell in row-major order
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii(".O\nO.", 'O', '.');
+    /// assert_eq!(grid.live_cells().collect::<Vec<_>>(), [(1, 0), (0, 1)]);
+    /// ```
+    pub fn live_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.coords_where(|cell| cell.is_alive())
+    }
+    /// Iterates over the `x` and `y` coordinates of every cell that is not alive, including
+    /// dying cells, in row-major order
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii(".O\nO.", 'O', '.');
+    /// assert_eq!(grid.dead_cells().collect::<Vec<_>>(), [(0, 0), (1, 1)]);
+    /// ```
+    pub fn dead_cells(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.coords_where(|cell| !cell.is_alive())
+    }
+    fn coords_where(
+        &self,
+        keep: impl Fn(&CellState) -> bool + 'static,
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(move |(_, cell)| keep(cell))
+            .map(move |(idx, _)| (idx % width, idx / width))
+    }
+    fn live_points(&self) -> Vec<Point> {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.is_alive())
+            .map(|(idx, _)| self.pos(idx))
+            .collect()
+    }
+    /// Returns the top left and bottom right corners (inclusive) of the smallest box containing
+    /// every live cell, or `None` if there are no live cells.
+    /// ```
+    /// use gridlife::{Grid, Point};
+    /// let grid = Grid::from_ascii("....\n.O..\n..O.\n....", 'O', '.');
+    /// assert_eq!(grid.live_bounds(), Some((Point::new(1, 1), Point::new(2, 2))));
+    /// ```
+    pub fn live_bounds(&self) -> Option<(Point, Point)> {
+        let mut points = self.live_points().into_iter();
+        let first = points.next()?;
+        Some(points.fold((first, first), |(min, max), p| {
+            (
+                Point::new(min.x.min(p.x), min.y.min(p.y)),
+                Point::new(max.x.max(p.x), max.y.max(p.y)),
+            )
+        }))
+    }
+    /// Returns a new `Grid` containing only the region inside `live_bounds`, keeping the glyphs,
+    /// rules and topology. A grid with no live cells crops to an empty 0x0 grid.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii(".....\n..O..\n...O.\n.OOO.\n.....", 'O', '.');
+    /// assert_eq!(grid.crop_to_content().to_string(), ".O.\n..O\nOOO\n");
+    /// ```
+    pub fn crop_to_content(&self) -> Grid<CellState> {
+        let (min, max) = match self.live_bounds() {
+            Some(bounds) => bounds,
+            None => (Point::new(0, 0), Point::new(-1, -1)),
+        };
+        let width = (max.x - min.x + 1) as usize;
+        let height = (max.y - min.y + 1) as usize;
+        let mut grid = Self::empty_with_glyphs(width, height, self.alive_glyph, self.dead_glyph);
+        grid.rules = self.rules;
+        grid.rule = self.rule.clone();
+        grid.states = self.states;
+        grid.dying_glyph = self.dying_glyph;
+        grid.topology = self.topology;
+        grid.range = self.range;
+        for y in 0..height {
+            let src = self.idx(&Point::new(min.x, min.y + y as Coord));
+            grid.cells[y * width..(y + 1) * width].copy_from_slice(&self.cells[src..src + width]);
+            grid.age[y * width..(y + 1) * width].copy_from_slice(&self.age[src..src + width]);
+        }
+        grid.population = grid.calculate_population();
+        grid
+    }
+    /// Advances the grid up to `n` generations, returning how many generations were run.
+    /// Stops early once a generation leaves the grid unchanged, as every following generation
+    /// would be identical. The final population is available in `population`.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_empty(3, 3);
+    /// // An empty grid is stable after a single step
+    /// assert_eq!(grid.step_n(10), 1);
+    /// ```
+    pub fn step_n(&mut self, n: usize) -> usize {
+        for step in 1..=n {
+            if !self.step() {
+                return step;
+            }
+        }
+        n
+    }
+    /// Advances the grid up to `generations` generations like `step_n`, returning the min, max
+    /// and mean population across the starting generation and every generation run.
+    /// The grid is left at its final state.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii(".....\n..O..\n..O..\n..O..\n.....", 'O', '.');
+    /// let stats = grid.run_stats(4);
+    /// assert_eq!((stats.min, stats.max, stats.generations), (3, 3, 4));
+    /// ```
+    pub fn run_stats(&mut self, generations: usize) -> RunStats {
+        let mut min = self.population;
+        let mut max = self.population;
+        let mut total = self.population;
+        let mut run = 0;
+        while run < generations {
+            let changed = self.step();
+            run += 1;
+            min = min.min(self.population);
+            max = max.max(self.population);
+            total += self.population;
+            if !changed {
+                break;
+            }
+        }
+        RunStats {
+            min,
+            max,
+            mean: total as f64 / (run + 1) as f64,
+            generations: run,
+        }
+    }
+    /// Re-generates the state of the `Grid` like `update_states`, returning whether any cell
+    /// changed state. Run to a fixed point with `while grid.step() {}`, bearing in mind that
+    /// oscillating patterns never reach one.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("OO\nO.", 'O', '.');
+    /// assert!(grid.step());
+    /// assert!(!grid.step());
+    /// ```
+    pub fn step(&mut self) -> bool {
+        let (new_grid, population) = self.next_cells();
+        let changed = self
+            .cells
+            .iter()
+            .zip(&new_grid)
+            .any(|(old, new)| !old.same_state(new));
+        self.apply_next(new_grid, population);
+        changed
+    }
+    /// Re-generates the state of the `Grid` like `update_states`, then randomly kills live
+    /// cells until the population is at most `cap`.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_random(10, 10);
+    /// let mut rng = fastrand::Rng::with_seed(7);
+    /// grid.update_states_capped(5, &mut rng);
+    /// assert!(grid.population <= 5);
+    /// ```
+    pub fn update_states_capped(&mut self, cap: usize, rng: &mut fastrand::Rng) -> &[CellState] {
+        self.update_states();
+        if self.population > cap {
+            let mut alive: Vec<usize> = (0..self.cells.len())
+                .filter(|&idx| self.cells[idx].is_alive())
+                .collect();
+            rng.shuffle(&mut alive);
+            for &idx in &alive[..self.population - cap] {
+                self.cells[idx] = CellState::Dead(self.dead_glyph);
+                self.age[idx] = 0;
+            }
+            self.population = cap;
+        }
+        &self.cells
+    }
+    fn calculate_population(&self) -> usize {
+        self.cells.iter().filter(|c| c.is_alive()).count()
+    }
+    /// Gets the new state of the current cell based on the grid's `rules` and `states`.
+    /// By default these are the rules of Conways game of life:
+    /// - Any live cell with 0 or 1 live neighbors becomes dead, because of underpopulation
+    /// - Any live cell with 2 or 3 live neighbors stays alive, because its neighborhood is just right
+    /// - Any live cell with more than 3 live neighbors becomes dead, because of overpopulation
+    /// - Any dead cell with exactly 3 live neighbors becomes alive, by reproduction
+    fn get_cell_state(&self, cell: &CellState, state: NeighbourState) -> CellState {
+        if let Some(rule) = &self.rule {
+            return match rule.next(*cell, state.alive as u32) {
+                CellState::Alive(_) => CellState::Alive(self.alive_glyph),
+                CellState::Dead(_) => CellState::Dead(self.dead_glyph),
+                CellState::Dying(_, remaining) => CellState::Dying(self.dying_glyph, remaining),
+            };
+        }
+        let alive = state.alive as u32;
+        match cell {
+            CellState::Alive(_) if self.rules.is_survival(alive) => {
+                CellState::Alive(self.alive_glyph)
+            }
+            CellState::Alive(_) if self.states > 2 => {
+                CellState::Dying(self.dying_glyph, self.states - 2)
+            }
+            CellState::Alive(_) => CellState::Dead(self.dead_glyph),
+            CellState::Dying(_, remaining) if *remaining > 1 => {
+                CellState::Dying(self.dying_glyph, remaining - 1)
+            }
+            CellState::Dying(..) => CellState::Dead(self.dead_glyph),
+            CellState::Dead(_) if self.rules.is_birth(alive) => CellState::Alive(self.alive_glyph),
+            CellState::Dead(_) => *cell,
+        }
+    }
+    fn get_neighbours_state(&self, point: Point) -> NeighbourState {
+        let mut alive = 0;
+        let mut dead = 0;
+        if !self.contains(&point) {
+            // Not a cell, so it has no neighbours
+            return NeighbourState { alive, dead };
+        }
+        for neighbour in Point::neighbourhood(self.range)
+            .map(move |d| point + d)
+            .map(|p| {
+                self.topology
+                    .resolve(p, self.width, self.height)
+                    .map(|p| &self[p])
+            })
+        {
+            match neighbour {
+                Some(c) if c.is_alive() => alive += 1,
+                Some(_) => dead += 1,
+                None if self.outside_cell().is_alive() => alive += 1,
+                None => dead += 1,
+            }
+        }
+        NeighbourState { alive, dead }
+    }
+    /// Counts the neighbours of a cell that is not on any edge of the grid, so all eight
+    /// neighbours are known to be in bounds and can be indexed directly
+    #[inline]
+    fn get_interior_neighbours_state(&self, idx: usize) -> NeighbourState {
+        let above = idx - self.width;
+        let below = idx + self.width;
+        let alive = [
+            above - 1,
+            above,
+            above + 1,
+            idx - 1,
+            idx + 1,
+            below - 1,
+            below,
+            below + 1,
+        ]
+        .into_iter()
+        .filter(|&n| self.cells[n].is_alive())
+        .count() as i32;
+        NeighbourState {
+            alive,
+            dead: 8 - alive,
+        }
+    }
+    /// Writes the grid to `out` like its `Display` implementation, but with the dead cells at
+    /// the end of each row left off, so a grid with a space `dead_glyph` produces no trailing
+    /// whitespace
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii("X  \n X \n   ", 'X', ' ');
+    /// let mut out = String::new();
+    /// grid.write_trimmed(&mut out).unwrap();
+    /// assert_eq!(out, "X\n X\n\n");
+    /// ```
+    pub fn write_trimmed(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        self.write_rows(out, true)
+    }
+
+    fn write_rows(&self, out: &mut impl fmt::Write, trim: bool) -> fmt::Result {
+        let cell_width = self.cell_width();
+        for row in 0..self.height {
+            let mut cells = &self.cells[row * self.width..(row + 1) * self.width];
+            if trim {
+                let end = cells
+                    .iter()
+                    .rposition(|c| !c.is_dead())
+                    .map_or(0, |i| i + 1);
+                cells = &cells[..end];
+            }
+            for (i, cell) in cells.iter().enumerate() {
+                write!(out, "{cell}")?;
+                // Padding after the last cell of a trimmed row would be trailing whitespace
+                if !trim || i + 1 < cells.len() {
+                    for _ in cell.width()..cell_width {
+                        write!(out, " ")?;
+                    }
+                }
+            }
+            writeln!(out)?;
+        }
+        Ok(())
+    }
+
+    /// Number of terminal columns each cell is displayed in, which is the width of the widest
+    /// of the grid's glyphs, including the `QuadLife` palette. Narrower glyphs are padded with
+    /// trailing spaces to this width so that columns line up when mixing, for example, emoji
+    /// with ASCII.
+    ///
+    /// Only the glyphs are measured rather than every cell, so a cell given some other wider
+    /// glyph directly through `cells` is not padded for.
+    /// ```
+    /// use gridlife::Grid;
+    /// assert_eq!(Grid::new_empty(3, 3).cell_width(), 1);
+    /// assert_eq!(Grid::new_empty_custom_glyphs(3, 3, '🦠', ' ').cell_width(), 2);
+    /// ```
+    pub fn cell_width(&self) -> usize {
+        let palette = match self.color_mode {
+            ColorMode::QuadLife(palette) => palette,
+            _ => [self.alive_glyph; 4],
+        };
+        [
+            self.alive_glyph,
+            self.dead_glyph,
+            self.dying_glyph,
+            self.background.glyph(),
+        ]
+        .iter()
+        .chain(&palette)
+        .map(|c| c.width().unwrap_or(0))
+        .max()
+        .unwrap_or(1)
+        .max(1)
+    }
+}
+
+/// Checks that the alive and dead glyphs can be told apart and are safe to print
+fn check_glyphs(alive: char, dead: char) -> Result<(), GridError> {
+    if alive == dead || alive.is_control() || dead.is_control() {
+        return Err(GridError::InvalidGlyphs { alive, dead });
+    }
+    Ok(())
+}
+
+/// Checks that every neighbour count for `range` fits in a `Ruleset`
+fn check_range(range: u32) -> Result<(), GridError> {
+    if !(1..=MAX_RANGE).contains(&range) {
+        return Err(GridError::InvalidRange { range });
+    }
+    Ok(())
+}
+
+impl Default for Grid<CellState> {
+    fn default() -> Self {
+        Self::new_empty(10, 10)
+    }
+}
+
+/// Leaves out `neighbour_table`, which is only a cache, so stepping does not change the output
+/// of an otherwise unchanged grid. `rule` is only shown when one is set.
+impl<T: fmt::Debug> fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut grid = f.debug_struct("Grid");
+        grid.field("width", &self.width)
+            .field("height", &self.height)
+            .field("cells", &self.cells)
+            .field("dead_glyph", &self.dead_glyph)
+            .field("alive_glyph", &self.alive_glyph)
+            .field("population", &self.population)
+            .field("rules", &self.rules)
+            .field("states", &self.states)
+            .field("dying_glyph", &self.dying_glyph)
+            .field("topology", &self.topology)
+            .field("range", &self.range)
+            .field("color_mode", &self.color_mode)
+            .field("history_limit", &self.history_limit)
+            .field("metadata", &self.metadata)
+            .field("background", &self.background)
+            .field("age", &self.age)
+            .field("history", &self.history)
+            .field("activity", &self.activity)
+            .field("peak_population", &self.peak_population)
+            .field("last_changes", &self.last_changes)
+            .field("generation", &self.generation);
+        if let Some(rule) = &self.rule {
+            grid.field("rule", rule);
+        }
+        grid.finish()
+    }
+}
+
+/// Parses a grid with `Grid::from_str_auto`
+impl FromStr for Grid<CellState> {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(Self::from_str_auto(s))
+    }
+}
+
 impl Display for Grid<CellState> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in 0..self.height {
-            for w in row * self.width..(row + 1) * self.width {
-                write!(f, "{}", self.cells[w])?;
+        self.write_rows(f, false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grid_cell_out_of_bounds() {
+        let mut g = Grid::new_empty(3, 2);
+        assert_eq!(
+            g.cell(Point::new(3, 0)),
+            Err(GridError::OutOfBounds { x: 3, y: 0 })
+        );
+        assert_eq!(
+            g.cell(Point::new(0, -1)),
+            Err(GridError::OutOfBounds { x: 0, y: -1 })
+        );
+        // Would alias the first cell of the next row without the bounds check
+        assert!(g.cell_mut(Point::new(-1, 1)).is_err());
+        *g.cell_mut(Point::new(2, 1)).unwrap() = CellState::Alive('X');
+        assert!(g.cells[5].is_alive());
+        assert_eq!(
+            GridError::OutOfBounds { x: 4, y: 1 }.to_string(),
+            "cell (4, 1) is outside the grid"
+        );
+        assert_eq!(
+            GridError::InvalidRange { range: 0 }.to_string(),
+            "neighbour range 0 is not between 1 and 5"
+        );
+        assert_eq!(
+            GridError::DimensionMismatch {
+                expected: (3, 4),
+                found: (4, 3),
+            }
+            .to_string(),
+            "expected a 3x4 grid but found 4x3"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "cell (3, 0) is outside a 3x2 grid")]
+    fn test_grid_index_point_out_of_bounds() {
+        let g = Grid::new_empty(3, 2);
+        let _ = g[Point::new(3, 0)];
+    }
+
+    #[test]
+    fn test_grid_try_get() {
+        let g = Grid::new_empty(0, 0);
+        assert!(g.try_get(Point { x: 10, y: 10 }).is_none());
+    }
+
+    #[test]
+    fn test_grid_new_random_population() {
+        let g = Grid::new_random(20, 20);
+        assert_eq!(g.population, g.calculate_population());
+    }
+
+    #[test]
+    fn test_grid_new_random() {
+        let rand_g = Grid::new_random(10, 10);
+        assert_eq!(rand_g.cells.len(), 100);
+    }
+
+    #[test]
+    fn test_new_random_region_clamps() {
+        let g = Grid::new_random_region(4, 3, (2, 1, 10, 10), 1.0);
+        assert_eq!(g.to_string(), "    \n  XX\n  XX\n");
+        assert_eq!(g.population, 4);
+        let g = Grid::new_random_region(4, 3, (5, 0, 2, 2), 1.0);
+        assert_eq!(g.population, 0);
+    }
+
+    #[test]
+    fn test_update_region() {
+        let mut g = Grid::from_ascii("..........\n.OOO...O..\n.......O..\n.......O..", 'O', '.');
+        g.update_region((1, 1, 3, 1));
+        assert_eq!(g.population, 6);
+        assert_eq!(
+            g.to_string(),
+            "..O.......\n..O....O..\n..O....O..\n.......O..\n"
+        );
+        // A region past the edges is clamped and stepping it all matches update_states
+        let mut whole = g.clone();
+        whole.update_states();
+        g.update_region((0, 0, 20, 20));
+        assert!(g.same_pattern(&whole));
+        assert_eq!(g.population, whole.population);
+        // Cells edited directly leave the population stale, which must not underflow
+        let mut g = Grid::from_ascii("O..\n...\n...", 'O', '.');
+        g.population = 0;
+        g.update_region((0, 0, 1, 1));
+        assert_eq!(g.population, 0);
+    }
+
+    #[test]
+    fn test_analyze_motion_keeps_peak_population() {
+        let g = Grid::from_ascii(".....\n..O..\n.OOO.\n.....\n.....", 'O', '.');
+        assert_eq!(g.analyze_motion(2, 0), Motion::Unknown);
+        assert_eq!((g.peak_population(), g.change_rate()), (4, 0.0));
+    }
+
+    #[test]
+    fn test_peak_population() {
+        let mut g = Grid::from_ascii("OOO", 'O', '.');
+        assert_eq!(g.peak_population(), 3);
+        g.update_states();
+        g.update_states();
+        assert_eq!((g.population, g.peak_population()), (0, 3));
+        g.reset_peak();
+        assert_eq!(g.peak_population(), 0);
+    }
+
+    #[test]
+    fn test_invert() {
+        let mut g = Grid::from_ascii(".O.\n..O\nOOO", 'O', '.');
+        g.invert();
+        assert_eq!(g.to_string(), "O.O\nOO.\n...\n");
+        assert_eq!(g.population, 4);
+        g.invert();
+        assert!(g.matches_ascii(".O.\n..O\nOOO", 'O'));
+        assert_eq!(g.population, 5);
+        // A population left stale by writing cells directly is recounted
+        g.cells.fill(CellState::Alive('O'));
+        g.invert();
+        assert_eq!(g.population, 0);
+    }
+
+    #[test]
+    fn test_new_checkerboard() {
+        let g = Grid::new_checkerboard(3, 3);
+        let (a, d) = (CellState::Alive('X'), CellState::Dead(' '));
+        assert_eq!(g.cells, [a, d, a, d, a, d, a, d, a]);
+        assert_eq!(g.population, 5);
+        assert_eq!(Grid::new_stripes(3, 1, 0).population, 2);
+    }
+
+    #[test]
+    fn test_check_glyphs() {
+        assert!(check_glyphs('X', ' ').is_ok());
+        assert_eq!(
+            check_glyphs('X', 'X'),
+            Err(GridError::InvalidGlyphs {
+                alive: 'X',
+                dead: 'X'
+            })
+        );
+        assert!(check_glyphs('\n', '.').is_err());
+        assert!(check_glyphs('#', '\x1b').is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "alive and dead glyphs are both '#'")]
+    fn test_new_empty_custom_glyphs_same_glyphs() {
+        Grid::new_empty_custom_glyphs(2, 2, '#', '#');
+    }
+
+    #[test]
+    fn test_copies_do_not_check_glyphs() {
+        let mut g = Grid::from_ascii("O.\n.O", 'O', '.');
+        assert!(g.matches_ascii(" x\nx ", ' '));
+        g.dead_glyph = 'O';
+        assert_eq!(g.with_ghost_border().width, 4);
+        assert_eq!(g.crop_to_content().width, 2);
+        assert_eq!(g.centered_on(3, 3).population, 2);
+    }
+
+    #[test]
+    fn test_change_rate() {
+        let mut g = Grid::from_ascii("OO..\nOO..\n....\n....", 'O', '.');
+        g.update_states();
+        assert_eq!(g.change_rate(), 0.0);
+        // A lone cell dies next to the block
+        g.set(Point::new(3, 3), true);
+        g.update_states();
+        assert_eq!(g.change_rate(), 1.0 / 16.0);
+        assert_eq!(Grid::new_empty(0, 0).change_rate(), 0.0);
+    }
+
+    #[test]
+    fn test_dead_cells() {
+        let mut g = Grid::from_ascii("OOO\nO.O\nOO.", 'O', '.');
+        assert_eq!(g.dead_cells().collect::<Vec<_>>(), [(1, 1), (2, 2)]);
+        assert_eq!(g.live_cells().count(), 7);
+        g.cells[1] = CellState::Dying('+', 1);
+        assert_eq!(g.dead_cells().collect::<Vec<_>>(), [(1, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn test_set_many_glider() {
+        let glider = [(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)];
+        let mut g = Grid::new_empty(5, 5);
+        g.set_alive_many(&glider);
+        assert!(g.matches_ascii(".O.\n..O\nOOO", 'O'));
+        assert_eq!(g.population, 5);
+        g.set_dead_many(&[(1, 0), (2, 1), (9, 9)]);
+        assert_eq!(g.population, 3);
+        assert_eq!(g.live_cells().collect::<Vec<_>>(), glider[2..]);
+    }
+
+    #[test]
+    fn test_from_coords_ignores_out_of_bounds() {
+        let g = Grid::from_coords(2, 2, vec![(0, 0), (2, 0), (0, 5), (1, 1), (1, 1)]);
+        assert_eq!(g.to_string(), "X \n X\n");
+        assert_eq!(g.population, 2);
+    }
+
+    #[test]
+    fn test_generation_parity() {
+        let mut g = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+        g.history_limit = 2;
+        g.step_n(3);
+        assert_eq!((g.generation(), g.generation_parity()), (3, true));
+        g.step_back();
+        assert_eq!((g.generation(), g.generation_parity()), (2, false));
+        g.analyze_motion(2, 0);
+        assert_eq!(g.generation(), 2);
+        g.update_states();
+        g.reset_generation();
+        assert_eq!((g.generation(), g.generation_parity()), (0, false));
+    }
+
+    #[test]
+    fn test_live_background() {
+        // Empty cells are born next to 0, 3 or 5 live cells, and only cells with eight survive
+        let mut g = Grid::new_empty_with_rules(3, 3, Ruleset::new(&[0, 3, 5], &[8]));
+        let mut dead_background = g.clone();
+        g.background = CellState::Alive('X');
+        assert_eq!(g.get_neighbours_state(Point::new(0, 0)).alive, 5);
+        g.step_n(2);
+        assert!(g.is_full());
+        dead_background.step_n(2);
+        assert_eq!(dead_background.to_string(), "   \n X \n   \n");
+        // Changing the glyphs keeps the background in step with the cells
+        g.set_alive_glyph('#');
+        assert_eq!(
+            g.cell_or_background(Point::new(-1, 0)),
+            CellState::Alive('#')
+        );
+        dead_background.set_dead_glyph('.');
+        assert_eq!(
+            dead_background.with_ghost_border().to_string(),
+            ".....\n.....\n..X..\n.....\n.....\n"
+        );
+    }
+
+    #[test]
+    fn test_new_random_seeded() {
+        let grid = Grid::new_random_seeded(16, 16, 7);
+        assert_eq!(
+            grid.to_string(),
+            Grid::new_random_seeded(16, 16, 7).to_string()
+        );
+        assert_ne!(
+            grid.to_string(),
+            Grid::new_random_seeded(16, 16, 8).to_string()
+        );
+        assert_eq!(grid.population, grid.live_cells().count());
+    }
+
+    #[test]
+    fn test_range_two_neighbours() {
+        let mut g = Grid::new_empty(5, 5);
+        g.invert();
+        g.set_range(2).unwrap();
+        let centre = g.get_neighbours_state(Point::new(2, 2));
+        assert_eq!((centre.alive, centre.dead), (24, 0));
+        // Only the 3x3 box in the corner is inside a bounded grid
+        let corner = g.get_neighbours_state(Point::new(0, 0));
+        assert_eq!((corner.alive, corner.dead), (8, 16));
+        g.topology = Topology::Toroidal;
+        assert_eq!(g.get_neighbours_state(Point::new(0, 0)).alive, 24);
+        g.topology = Topology::Bounded;
+        assert_eq!(g.neighbour_counts()[..5], [8, 11, 14, 11, 8]);
+        // Only a cell with all 24 neighbours alive survives
+        g.rules = Ruleset::new(&[], &[24]);
+        g.update_states();
+        assert_eq!(g.to_string(), "     \n     \n  X  \n     \n     \n");
+    }
+
+    #[test]
+    fn test_get_neighbours_state() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[1] = CellState::Alive(g.alive_glyph);
+        // x 0 x
+        // x x x
+        // x x x
+        let state = g.get_neighbours_state(Point { x: 0, y: 0 });
+        assert_eq!(state.dead, 7);
+        assert_eq!(state.alive, 1);
+    }
+
+    #[test]
+    fn test_get_neighbours_state_corners() {
+        let mut g = Grid::from_ascii("O..\n...\n.OO", 'O', '.');
+        let corners = [(0, 0), (2, 0), (0, 2), (2, 2)];
+        for (topology, alive) in [
+            (Topology::Bounded, [0, 0, 1, 1]),
+            (Topology::BoundedAlive, [5, 5, 6, 6]),
+            (Topology::Toroidal, [2, 3, 3, 2]),
+            (
+                Topology::Cylinder {
+                    wrap_x: true,
+                    wrap_y: false,
+                },
+                [0, 1, 2, 1],
+            ),
+        ] {
+            g.topology = topology;
+            for (&(x, y), alive) in corners.iter().zip(alive) {
+                let state = g.get_neighbours_state(Point { x, y });
+                assert_eq!(state.alive, alive, "{topology:?} at ({x}, {y})");
+                assert_eq!(state.dead, 8 - alive, "{topology:?} at ({x}, {y})");
+            }
+        }
+    }
+
+    #[test]
+    fn test_get_neighbours_state_unknown_point() {
+        let g = Grid::new_empty(3, 3);
+        let state = g.get_neighbours_state(Point { x: 5, y: 5 });
+        assert_eq!(state.dead, 0);
+        assert_eq!(state.alive, 0);
+    }
+
+    #[test]
+    fn test_grid_display() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[4] = CellState::Alive('X');
+        let s = format!("{}", g);
+        assert_eq!(s, "   \n X \n   \n".to_string());
+    }
+
+    #[test]
+    fn test_write_trimmed() {
+        let mut g = Grid::from_ascii("..O..\n.....\nO.+..", 'O', '.');
+        g.cells[12] = CellState::Dying('+', 1);
+        let mut out = String::new();
+        g.write_trimmed(&mut out).unwrap();
+        assert_eq!(out, "..O\n\nO.+\n");
+        assert_eq!(g.to_string(), "..O..\n.....\nO.+..\n");
+
+        // Wide glyphs are still padded between cells but not at the end
+        let mut g = Grid::new_empty_custom_glyphs(3, 1, '🦠', ' ');
+        g.set(Point::new(1, 0), true);
+        g.cells[0] = CellState::Alive('x');
+        let mut out = String::new();
+        g.write_trimmed(&mut out).unwrap();
+        assert_eq!(out, "x 🦠\n");
+    }
+
+    #[test]
+    fn test_grid_debug() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[4] = CellState::Alive('X');
+        let s = format!("{:?}", g);
+        assert_eq!(s, "Grid { width: 3, height: 3, cells: [Dead(' '), Dead(' '), Dead(' '), Dead(' '), Alive('X'), Dead(' '), Dead(' '), Dead(' '), Dead(' ')], dead_glyph: ' ', alive_glyph: 'X', population: 0, rules: Ruleset { birth: 8, survive: 12 }, states: 2, dying_glyph: '+', topology: Bounded, range: 1, color_mode: Single, history_limit: 0, metadata: PatternMetadata { name: None, author: None, comments: [] }, background: Dead(' '), age: [0, 0, 0, 0, 0, 0, 0, 0, 0], history: [], activity: [0, 0, 0, 0, 0, 0, 0, 0, 0], peak_population: 0, last_changes: 0, generation: 0 }".to_string());
+        // Stepping only fills in caches, which are not shown
+        g.cells[4] = CellState::Dead(' ');
+        let before = format!("{:?}", g);
+        g.update_states();
+        g.reset_generation();
+        assert_eq!(format!("{:?}", g), before);
+    }
+
+    #[test]
+    fn test_update_state() {
+        let mut g = Grid::new_random(10, 10);
+        g.update_states();
+    }
+
+    #[test]
+    fn test_get_cell_state() {
+        let g = Grid::new_empty(3, 3);
+        // Any live cell with 0 or 1 live neighbors becomes dead, because of underpopulation
+        assert_eq!(
+            g.get_cell_state(&CellState::Alive('X'), NeighbourState { alive: 1, dead: 0 }),
+            CellState::Dead(' ')
+        );
+        //Any live cell with 2 or 3 live neighbors stays alive, because its neighborhood is just right
+        assert_eq!(
+            g.get_cell_state(&CellState::Alive('X'), NeighbourState { alive: 3, dead: 0 }),
+            CellState::Alive('X')
+        );
+        // Any live cell with more than 3 live neighbors becomes dead, because of overpopulation
+        assert_eq!(
+            g.get_cell_state(&CellState::Alive('X'), NeighbourState { alive: 5, dead: 1 }),
+            CellState::Dead(' ')
+        );
+        // Any dead cell with exactly 3 live neighbors becomes alive, by reproduction
+        assert_eq!(
+            g.get_cell_state(&CellState::Dead(' '), NeighbourState { alive: 3, dead: 0 }),
+            CellState::Alive('X')
+        );
+    }
+
+    #[test]
+    fn test_new_random_custom_glyphs() {
+        let g = Grid::new_random_custom_glyphs(3, 3, 'A', 'D');
+        assert_eq!(g.cells.len(), 9);
+        let unexpected_states: Vec<&CellState> = g
+            .cells
+            .iter()
+            .filter(|c| {
+                let state = c.to_string();
+                state != "A" && state != "D"
+            })
+            .collect();
+        assert_eq!(unexpected_states.len(), 0);
+    }
+
+    #[test]
+    fn test_to_heat_string() {
+        let mut g = Grid::new_empty(3, 2);
+        g.cells[0] = CellState::Alive('X');
+        g.cells[1] = CellState::Alive('X');
+        g.cells[2] = CellState::Alive('X');
+        g.age = vec![0, 1, 5, 0, 0, 0];
+        assert_eq!(g.to_heat_string(&['a', 'b', 'c']), "abc\n   \n");
+        assert_eq!(g.to_heat_string(&[]), "XXX\n   \n");
+    }
+
+    #[test]
+    fn test_ages_reset_on_death() {
+        let mut g = Grid::new_empty(3, 3);
+        // Blinker: the centre survives while the ends die and are reborn elsewhere
+        g.cells[3] = CellState::Alive('X');
+        g.cells[4] = CellState::Alive('X');
+        g.cells[5] = CellState::Alive('X');
+        g.update_states();
+        assert_eq!(g.ages(), &[0, 0, 0, 0, 1, 0, 0, 0, 0]);
+        assert_eq!(g.to_heat_string(&['n', 'o']), " n \n o \n n \n");
+    }
+
+    #[test]
+    fn test_remove_isolated() {
+        let mut g = Grid::new_empty(5, 5);
+        // Block in the top left and a lone cell in the bottom right
+        for idx in [0, 1, 5, 6, 24] {
+            g.cells[idx] = CellState::Alive('X');
+        }
+        g.remove_isolated();
+        assert_eq!(g.population, 4);
+        assert_eq!(g.cells[24], CellState::Dead(' '));
+        for idx in [0, 1, 5, 6] {
+            assert_eq!(g.cells[idx], CellState::Alive('X'));
+        }
+    }
+
+    #[test]
+    fn test_ages_increment_for_stable_block() {
+        let mut g = Grid::new_empty(4, 4);
+        for idx in [5, 6, 9, 10] {
+            g.cells[idx] = CellState::Alive('X');
+        }
+        g.update_states();
+        assert!([5, 6, 9, 10].iter().all(|&idx| g.ages()[idx] == 1));
+        g.update_states();
+        assert!([5, 6, 9, 10].iter().all(|&idx| g.ages()[idx] == 2));
+        assert_eq!(g.ages().iter().filter(|&&a| a > 0).count(), 4);
+    }
+
+    #[test]
+    fn test_population_with_mixed_glyphs() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[0] = CellState::Alive('X');
+        g.cells[1] = CellState::Alive('#');
+        g.cells[2] = CellState::Alive('O');
+        g.cells[3] = CellState::Dead('.');
+        assert_eq!(g.calculate_population(), 3);
+        // The horizontal line of mixed glyphs becomes a vertical one of the grid's glyph
+        g.update_states();
+        assert_eq!(g.population, 2);
+        assert_eq!(g.cells[1], CellState::Alive('X'));
+        assert_eq!(g.cells[4], CellState::Alive('X'));
+    }
+
+    #[test]
+    fn test_cell_state_is_alive() {
+        assert!(CellState::Alive('X').is_alive());
+        assert!(CellState::Alive(' ').is_alive());
+        assert!(!CellState::Dead('X').is_alive());
+    }
+
+    #[test]
+    fn test_cell_state_is_dead() {
+        assert!(CellState::Dead(' ').is_dead());
+        assert!(CellState::Dead('X').is_dead());
+        assert!(!CellState::Alive(' ').is_dead());
+    }
+
+    #[test]
+    fn test_get_neighbours_state_mixed_glyphs() {
+        let mut g = Grid::new_empty(3, 3);
+        g.cells[0] = CellState::Alive('A');
+        g.cells[1] = CellState::Alive('B');
+        g.cells[3] = CellState::Dead('d');
+        let state = g.get_neighbours_state(Point::new(1, 1));
+        assert_eq!(state.alive, 2);
+        assert_eq!(state.dead, 6);
+    }
+
+    #[test]
+    fn test_evolve_to_tensor() {
+        let mut g = Grid::new_empty(3, 3);
+        for idx in [3, 4, 5] {
+            g.cells[idx] = CellState::Alive('X');
+        }
+        let (buffer, shape) = g.evolve_to_tensor(3);
+        assert_eq!(shape, [3, 3, 3]);
+        assert_eq!(buffer.len(), 3 * 3 * 3);
+        assert_eq!(&buffer[..9], &[0, 0, 0, 1, 1, 1, 0, 0, 0]);
+        assert_eq!(&buffer[9..18], &[0, 1, 0, 0, 1, 0, 0, 1, 0]);
+        assert_eq!(&buffer[18..], &buffer[..9]);
+    }
+
+    #[test]
+    fn test_try_new() {
+        let g = Grid::try_new(4, 2).unwrap();
+        assert_eq!(g.cells.len(), 8);
+        assert!(Grid::try_new(0, 0).is_ok());
+        assert!(Grid::try_new(3, 0).is_ok());
+    }
+
+    #[test]
+    fn test_try_new_zero_width() {
+        assert_eq!(
+            Grid::try_new(0, 5).unwrap_err(),
+            GridError::ZeroWidth { height: 5 }
+        );
+    }
+
+    #[test]
+    fn test_try_new_overflow() {
+        assert_eq!(
+            Grid::try_new(usize::MAX, 2).unwrap_err(),
+            GridError::TooLarge {
+                width: usize::MAX,
+                height: 2
             }
-            writeln!(f)?;
+        );
+        assert!(matches!(
+            Grid::try_new(1 << 31, 1),
+            Err(GridError::TooLarge { .. })
+        ));
+    }
+
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn test_idx_beyond_coord_max_cells() {
+        // Zero sized cells let a grid with more than `Coord::MAX` cells exist without memory
+        let (width, height) = (1 << 16, 1 << 16);
+        let g: Grid<()> = Grid::with_cells(width, height, vec![(); width * height]);
+        let last = Point::new(width as Coord - 1, height as Coord - 1);
+        assert!(g.contains(&last));
+        assert_eq!(g.idx(&last), width * height - 1);
+        assert_eq!(g.pos(width * height - 1), last);
+        assert_eq!(
+            g.pos(g.idx(&Point::new(12, 40_000))),
+            Point::new(12, 40_000)
+        );
+        assert!(g.get(last + Point::new(0, 1)).is_none());
+    }
+
+    #[test]
+    fn test_analyze_motion_block() {
+        let mut g = Grid::new_empty(4, 4);
+        for idx in [5, 6, 9, 10] {
+            g.cells[idx] = CellState::Alive('X');
         }
-        Ok(())
+        assert_eq!(g.analyze_motion(4, 2), Motion::Static);
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    #[test]
+    fn test_analyze_motion_blinker() {
+        let mut g = Grid::new_empty(5, 5);
+        for idx in [11, 12, 13] {
+            g.cells[idx] = CellState::Alive('X');
+        }
+        let before = g.cells.clone();
+        assert_eq!(g.analyze_motion(4, 2), Motion::Oscillator { period: 2 });
+        assert_eq!(g.cells, before);
+    }
 
     #[test]
-    fn test_grid_try_get() {
-        let g = Grid::new_empty(0, 0);
-        assert!(g.try_get(Point { x: 10, y: 10 }) == None);
+    fn test_analyze_motion_glider() {
+        let mut g = Grid::new_empty(10, 10);
+        // .O.
+        // ..O
+        // OOO
+        for idx in [1, 12, 20, 21, 22] {
+            g.cells[idx] = CellState::Alive('X');
+        }
+        assert_eq!(
+            g.analyze_motion(8, 2),
+            Motion::Spaceship {
+                period: 4,
+                dx: 1,
+                dy: 1
+            }
+        );
+        assert_eq!(g.analyze_motion(3, 2), Motion::Unknown);
     }
 
     #[test]
-    fn test_grid_new_random() {
-        let rand_g = Grid::new_random(10, 10);
-        assert_eq!(rand_g.cells.len(), 100);
+    fn test_step_n() {
+        let mut g = Grid::new_empty(5, 5);
+        for idx in [11, 12, 13] {
+            g.cells[idx] = CellState::Alive('X');
+        }
+        // Blinkers never stabilise so all steps are run
+        assert_eq!(g.step_n(5), 5);
+        assert_eq!(g.population, 3);
+        assert_eq!(g.step_n(0), 0);
     }
 
     #[test]
-    fn test_get_neighbours_state() {
+    fn test_step_n_stops_when_stable() {
+        let mut g = Grid::new_empty(5, 5);
+        // Three cells in an L shape become a block after one step
+        for idx in [6, 7, 11] {
+            g.cells[idx] = CellState::Alive('X');
+        }
+        assert_eq!(g.step_n(10), 2);
+        assert_eq!(g.population, 4);
+    }
+
+    #[test]
+    fn test_update_states_capped() {
+        let mut g = Grid::new_empty(20, 20);
+        // R-pentomino grows rapidly
+        // .OO
+        // OO.
+        // .O.
+        for (x, y) in [(9, 8), (10, 8), (8, 9), (9, 9), (9, 10)] {
+            g.cells[y * 20 + x] = CellState::Alive('X');
+        }
+        let mut rng = fastrand::Rng::with_seed(42);
+        for _ in 0..20 {
+            g.update_states_capped(8, &mut rng);
+            assert!(g.population <= 8);
+            assert_eq!(g.population, g.calculate_population());
+        }
+    }
+
+    #[test]
+    fn test_from_ascii() {
+        let g = Grid::from_ascii(".O.\n..O\nOOO", 'O', '.');
+        assert_eq!((g.width, g.height), (3, 3));
+        assert_eq!(g.population, 5);
+        assert_eq!(g.to_string(), ".O.\n..O\nOOO\n");
+    }
+
+    #[test]
+    fn test_from_ascii_pads_short_lines() {
+        let g = Grid::from_ascii("O\n.xO\n", 'O', '.');
+        assert_eq!((g.width, g.height), (3, 2));
+        assert_eq!(g.population, 2);
+        assert_eq!(g.to_string(), "O..\n..O\n");
+    }
+
+    #[test]
+    fn test_from_ascii_empty() {
+        let g = Grid::from_ascii("", 'O', '.');
+        assert_eq!((g.width, g.height, g.population), (0, 0, 0));
+    }
+
+    #[test]
+    fn test_from_str_auto() {
+        let g: Grid<CellState> = "X.\no O\n\n#*1-".parse().unwrap();
+        assert_eq!((g.width, g.height), (4, 4));
+        // Lower case `o` is dead
+        assert_eq!(g.population, 5);
+        assert_eq!(g.to_string(), "X   \n  X \n    \nXXX \n");
+        assert_eq!(Grid::from_str_auto("").cells.len(), 0);
+    }
+
+    #[test]
+    fn test_population_counted_while_stepping() {
+        let mut g = Grid::new_empty(16, 16);
+        g.randomize_seeded(0.4, 11);
+        g.states = 4;
+        g.topology = Topology::Toroidal;
+        for _ in 0..20 {
+            g.update_states();
+            assert_eq!(g.population, g.calculate_population());
+        }
+    }
+
+    #[test]
+    fn test_neighbour_counts() {
+        let mut g = Grid::from_ascii("O...\n.O..\n....", 'O', '.');
+        assert_eq!(g.neighbour_counts(), [1, 2, 1, 0, 2, 1, 1, 0, 1, 1, 1, 0]);
+        g.topology = Topology::Toroidal;
+        assert_eq!(g.neighbour_counts(), [1, 2, 1, 1, 2, 1, 1, 1, 2, 2, 1, 1]);
+        g.topology = Topology::BoundedAlive;
+        assert_eq!(g.neighbour_counts()[0], 6);
+    }
+
+    #[test]
+    fn test_matches_ascii() {
+        let mut g = Grid::from_ascii("......\n..O...\n...O..\n.OOO..\n......", 'O', '.');
+        g.step_n(4);
+        assert!(g.matches_ascii(".#.\n..#\n###", '#'));
+        // Shifted within the pattern's padding
+        assert!(g.matches_ascii("\n\n  X\n   X\n XXX\n", 'X'));
+        assert!(!g.matches_ascii("#..\n..#\n###", '#'));
+        assert!(!g.matches_ascii(".#.\n..#\n###\n#..", '#'));
+        assert!(Grid::new_empty(3, 3).matches_ascii("...", 'O'));
+        assert!(!Grid::new_empty(3, 3).matches_ascii("O", 'O'));
+    }
+
+    #[test]
+    fn test_centered_on() {
+        let g = Grid::from_ascii("OO\nO.", 'O', '.').centered_on(6, 4);
+        assert_eq!(g.to_string(), "......\n..OO..\n..O...\n......\n");
+        assert_eq!(g.population, 3);
+    }
+
+    #[test]
+    fn test_centered_on_clips_symmetrically() {
+        let g = Grid::from_ascii("O...O\n.O.O.\n..O..\n.O.O.\nO...O", 'O', '.').centered_on(3, 3);
+        assert_eq!(g.to_string(), "O.O\n.O.\nO.O\n");
+        assert_eq!(g.population, 5);
+    }
+
+    #[test]
+    fn test_point_arithmetic() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + Point::NORTH_EAST, Point::new(3, 2));
+        assert_eq!(p - Point::new(1, 1), Point::new(1, 2));
+        assert_eq!(-Point::SOUTH, Point::NORTH);
+        assert_eq!(Point::from((4, 5)), Point::new(4, 5));
+        let sum = Point::DIRECTIONS
+            .into_iter()
+            .fold(Point::new(0, 0), |acc, d| acc + d);
+        assert_eq!(sum, Point::new(0, 0));
+    }
+
+    #[test]
+    fn test_grid_get_set() {
         let mut g = Grid::new_empty(3, 3);
-        g.cells[1] = CellState::Alive(g.alive_glyph);
-        // x 0 x
-        // x x x
-        // x x x
-        let state = g.get_neighbours_state(Point { x: 0, y: 0 });
-        assert_eq!(state.dead, 2);
-        assert_eq!(state.alive, 1);
+        assert_eq!(g.set(Point::new(2, 1), true), Some(CellState::Dead(' ')));
+        assert_eq!(g.set(Point::new(2, 1), true), Some(CellState::Alive('X')));
+        assert_eq!(g.population, 1);
+        assert_eq!(g.get(Point::new(2, 1)), Some(&CellState::Alive('X')));
+        assert_eq!(g[Point::new(2, 1)], CellState::Alive('X'));
+        assert_eq!(g.set(Point::new(3, 1), true), None);
+        assert_eq!(g.set(Point::new(2, 1), false), Some(CellState::Alive('X')));
+        assert_eq!(g.population, 0);
     }
 
     #[test]
-    fn test_get_neighbours_state_unknown_point() {
-        let g = Grid::new_empty(3, 3);
-        let state = g.get_neighbours_state(Point { x: 5, y: 5 });
-        assert_eq!(state.dead, 0);
-        assert_eq!(state.alive, 0);
+    fn test_update_states_delta() {
+        let mut g = Grid::from_ascii(".....\n.....\n.OOO.\n.....\n.....", 'O', '.');
+        let mut changes = g.update_states_delta();
+        changes.sort_by_key(|&(x, y, _)| (x, y));
+        assert_eq!(
+            changes,
+            vec![
+                (1, 2, CellState::Dead('.')),
+                (2, 1, CellState::Alive('O')),
+                (2, 3, CellState::Alive('O')),
+                (3, 2, CellState::Dead('.')),
+            ]
+        );
+        assert_eq!(g.population, 3);
+        assert_eq!(g.to_string(), ".....\n..O..\n..O..\n..O..\n.....\n");
     }
 
     #[test]
-    fn test_grid_display() {
+    fn test_update_states_delta_stable() {
+        let mut g = Grid::from_ascii("OO\nOO", 'O', '.');
+        assert!(g.update_states_delta().is_empty());
+    }
+
+    #[test]
+    fn test_interior_neighbours_match_bounds_checked() {
+        let g = Grid::new_random(6, 5);
+        for y in 1..4 {
+            for x in 1..5 {
+                assert_eq!(
+                    g.get_interior_neighbours_state(y * 6 + x),
+                    g.get_neighbours_state(Point::new(x as i32, y as i32))
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_with_rules_constructors() {
+        let seeds = Ruleset::new(&[2], &[]);
+        let g = Grid::new_empty_with_rules(4, 2, seeds);
+        assert_eq!((g.width, g.height, g.population), (4, 2, 0));
+        assert_eq!((g.alive_glyph, g.dead_glyph), ('X', ' '));
+        assert_eq!(g.rules, seeds);
+        let g = Grid::new_random_with_rules(4, 2, seeds);
+        assert_eq!(g.cells.len(), 8);
+        assert_eq!(g.population, g.calculate_population());
+        assert_eq!(g.rules, seeds);
+    }
+
+    #[test]
+    fn test_update_states_with_rules() {
+        // HighLife B36/S23: a dead cell with six live neighbours is born
+        let mut g = Grid::from_ascii("OOO\n...\nOOO", 'O', '.');
+        g.update_states();
+        assert_eq!(g.cells[4], CellState::Dead('.'));
+        let mut g = Grid::from_ascii("OOO\n...\nOOO", 'O', '.');
+        g.rules = Ruleset::new(&[3, 6], &[2, 3]);
+        g.update_states();
+        assert_eq!(g.cells[4], CellState::Alive('O'));
+    }
+
+    #[test]
+    fn test_next_generation_matches_update_states() {
+        // A glider crossing the wrapped edges of a torus
+        let mut g = Grid::from_ascii(".O.\n..O\nOOO", 'O', '.').centered_on(6, 6);
+        g.topology = Topology::Toroidal;
+        for _ in 0..12 {
+            let next = Grid::next_generation(&g.cells, 6, 6, g.rules, g.topology);
+            g.update_states();
+            assert!(next.iter().zip(&g.cells).all(|(a, b)| a.same_state(b)));
+        }
+        assert_eq!(g.population, 5);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected 3x3 cells")]
+    fn test_next_generation_wrong_size() {
+        Grid::next_generation(&[], 3, 3, Ruleset::default(), Topology::Bounded);
+    }
+
+    #[test]
+    fn test_toroidal_neighbours() {
+        let mut g = Grid::from_ascii("O...\n....\n....\n...O", 'O', '.');
+        assert_eq!(g.get_neighbours_state(Point::new(0, 0)).alive, 0);
+        g.topology = Topology::Toroidal;
+        let state = g.get_neighbours_state(Point::new(0, 0));
+        assert_eq!((state.alive, state.dead), (1, 7));
+    }
+
+    #[test]
+    fn test_toroidal_glider_wraps() {
+        let mut g = Grid::from_ascii(".O...\n..O..\nOOO..\n.....\n.....", 'O', '.');
+        g.topology = Topology::Toroidal;
+        let start = g.to_string();
+        // A glider moves one cell diagonally every 4 generations, so returns after 20
+        for _ in 0..20 {
+            g.update_states();
+        }
+        assert_eq!(g.population, 5);
+        assert_eq!(g.to_string(), start);
+    }
+
+    #[test]
+    fn test_cell_state_is_dying() {
+        assert!(CellState::Dying('+', 1).is_dying());
+        assert!(!CellState::Dying('+', 1).is_alive());
+        assert!(!CellState::Dying('+', 1).is_dead());
+        assert_eq!(CellState::Dying('+', 1).to_string(), "+");
+    }
+
+    #[test]
+    fn test_brians_brain() {
+        // Brian's Brain: B2/S/C3
+        let mut g = Grid::from_ascii("....\n.OO.\n....", 'O', '.');
+        g.rules = Ruleset::new(&[2], &[]);
+        g.states = 3;
+        g.update_states();
+        assert_eq!(g.to_string(), ".OO.\n.++.\n.OO.\n");
+        assert_eq!(g.cells[5], CellState::Dying('+', 1));
+        assert_eq!(g.population, 4);
+        g.update_states();
+        // The dying cells are now dead and the newborn cells are dying
+        assert_eq!(g.cells[5], CellState::Dead('.'));
+        assert_eq!(g.cells[1], CellState::Dying('+', 1));
+    }
+
+    #[test]
+    fn test_generations_dying_countdown() {
+        let mut g = Grid::from_ascii("O", 'O', '.');
+        g.states = 4;
+        g.update_states();
+        assert_eq!(g.cells[0], CellState::Dying('+', 2));
+        g.update_states();
+        assert_eq!(g.cells[0], CellState::Dying('+', 1));
+        g.update_states();
+        assert_eq!(g.cells[0], CellState::Dead('.'));
+    }
+
+    #[test]
+    fn test_step_n_waits_for_dying_cells() {
+        let mut g = Grid::from_ascii("O", 'O', '.');
+        g.states = 4;
+        assert_eq!(g.step_n(10), 4);
+        assert_eq!(g.cells[0], CellState::Dead('.'));
+    }
+
+    #[test]
+    fn test_step_reports_changes() {
+        let mut g = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+        assert!(g.step());
+        assert!(g.step());
+        let mut g = Grid::from_ascii("OO\nOO", 'O', '.');
+        assert!(!g.step());
+        let mut g = Grid::from_ascii("O", 'O', '.');
+        assert!(g.step());
+        assert!(!g.step());
+    }
+
+    #[test]
+    fn test_grid_display_wide_glyphs() {
+        let mut g = Grid::new_empty_custom_glyphs(2, 2, '💀', '.');
+        g.set(Point::new(0, 0), true);
+        g.set(Point::new(1, 1), true);
+        assert_eq!(g.cell_width(), 2);
+        assert_eq!(g.to_string(), "💀. \n. 💀\n");
+    }
+
+    #[test]
+    fn test_cell_state_width() {
+        assert_eq!(CellState::Alive('X').width(), 1);
+        assert_eq!(CellState::Dead('💀').width(), 2);
+        assert_eq!(CellState::Dying('\u{7}', 1).width(), 0);
+        assert_eq!(CellState::Dying('+', 1).glyph(), '+');
+    }
+
+    #[test]
+    fn test_bounded_alive_edges() {
         let mut g = Grid::new_empty(3, 3);
-        g.cells[4] = CellState::Alive('X');
-        let s = format!("{}", g);
-        assert_eq!(s, "   \n X \n   \n".to_string());
+        g.topology = Topology::BoundedAlive;
+        assert_eq!(g.get_neighbours_state(Point::new(0, 0)).alive, 5);
+        assert_eq!(g.get_neighbours_state(Point::new(1, 0)).alive, 3);
+        assert_eq!(g.get_neighbours_state(Point::new(1, 1)).alive, 0);
+        // Only the middle of each edge has exactly three live neighbours
+        g.update_states();
+        assert_eq!(g.to_string(), " X \nX X\n X \n");
+        assert_eq!(g.population, 4);
     }
 
     #[test]
-    fn test_grid_debug() {
+    fn test_clear() {
+        let mut g = Grid::new_random_custom_glyphs(4, 4, '#', '.');
+        g.update_states();
+        g.clear();
+        assert_eq!(g.population, 0);
+        assert_eq!(g.cells.len(), 16);
+        assert!(g.cells.iter().all(|&c| c == CellState::Dead('.')));
+        assert!(g.ages().iter().all(|&a| a == 0));
+        assert_eq!(g.change_rate(), 0.0);
+        // The pattern from before clearing is not brought back
+        let mut g = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+        g.history_limit = 4;
+        g.update_states();
+        g.clear();
+        assert!(!g.step_back());
+        assert!(g.activity_map().iter().all(|&a| a == 0));
+    }
+
+    #[test]
+    fn test_randomize() {
+        let mut g = Grid::new_empty(20, 20);
+        g.randomize(0.0);
+        assert_eq!(g.population, 0);
+        g.randomize(1.0);
+        assert_eq!(g.population, 400);
+        g.randomize(0.5);
+        assert_eq!(g.population, g.calculate_population());
+        assert_eq!(g.cells.len(), 400);
+        // Stepping back after refilling would bring back the old cells
+        g.history_limit = 4;
+        g.update_states();
+        g.randomize(0.5);
+        assert!(!g.step_back());
+        assert_eq!(g.change_rate(), 0.0);
+        assert!(g.activity_map().iter().all(|&a| a == 0));
+    }
+
+    #[test]
+    fn test_randomize_seeded() {
+        let mut a = Grid::new_empty(8, 8);
+        let mut b = Grid::new_empty(8, 8);
+        a.randomize_seeded(0.3, 9);
+        b.randomize_seeded(0.3, 9);
+        assert_eq!(a.cells, b.cells);
+        assert!(a.population > 0 && a.population < 64);
+    }
+
+    #[test]
+    fn test_direction_offsets() {
+        for (dir, offset) in Direction::ALL.iter().zip(Point::DIRECTIONS.iter()) {
+            assert_eq!(dir.offset(), *offset);
+            assert_eq!(Point::from(*dir), *offset);
+        }
+    }
+
+    #[test]
+    fn test_neighbour() {
         let mut g = Grid::new_empty(3, 3);
-        g.cells[4] = CellState::Alive('X');
-        let s = format!("{:?}", g);
-        assert_eq!(s, "Grid { width: 3, height: 3, cells: [Dead(' '), Dead(' '), Dead(' '), Dead(' '), Alive('X'), Dead(' '), Dead(' '), Dead(' '), Dead(' ')], dead_glyph: ' ', alive_glyph: 'X', population: 0 }".to_string());
+        g.set(Point::new(0, 0), true);
+        assert!(g.neighbour(1, 1, Direction::NorthWest).unwrap().is_alive());
+        assert!(g.neighbour(1, 0, Direction::West).unwrap().is_alive());
+        assert!(g.neighbour(1, 1, Direction::North).unwrap().is_dead());
+        assert_eq!(g.neighbour(0, 0, Direction::West), None);
+        assert_eq!(g.neighbour(3, 0, Direction::West), None);
+        g.topology = Topology::Toroidal;
+        assert!(g.neighbour(2, 2, Direction::SouthEast).unwrap().is_alive());
+        assert!(g.neighbour(0, 2, Direction::South).unwrap().is_alive());
     }
 
     #[test]
-    fn test_update_state() {
-        let mut g = Grid::new_random(10, 10);
+    fn test_same_pattern() {
+        let a = Grid::from_ascii("X..\n.X.\n..X", 'X', '.');
+        let b = Grid::from_ascii("█  \n █ \n  █", '█', ' ');
+        assert!(a.same_pattern(&b));
+        assert!(b.same_pattern(&a));
+
+        let c = Grid::from_ascii("█  \n █ \n   ", '█', ' ');
+        assert!(!a.same_pattern(&c));
+        // Same population, so the cells have to be compared
+        let d = Grid::from_ascii("X.X\n.X.\n...", 'X', '.');
+        assert!(!a.same_pattern(&d));
+
+        // Same cell count and pattern in memory, but different dimensions
+        let wide = Grid::from_ascii("X.....", 'X', '.');
+        let tall = Grid::from_ascii("X..\n...", 'X', '.');
+        assert!(!wide.same_pattern(&tall));
+    }
+
+    #[test]
+    fn test_run_stats() {
+        // Two isolated cells die out: 2 -> 0 -> 0 (stable)
+        let mut g = Grid::from_ascii("O.O\n...\n...", 'O', '.');
+        let stats = g.run_stats(10);
+        assert_eq!(stats.min, 0);
+        assert_eq!(stats.max, 2);
+        assert_eq!(stats.generations, 2);
+        assert!((stats.mean - 2.0 / 3.0).abs() < f64::EPSILON);
+        assert_eq!(g.population, 0);
+
+        let mut g = Grid::from_ascii("OO\nO.", 'O', '.');
+        let stats = g.run_stats(0);
+        assert_eq!(stats.generations, 0);
+        assert_eq!((stats.min, stats.max), (3, 3));
+        assert_eq!(g.population, 3);
+    }
+
+    #[test]
+    fn test_live_bounds() {
+        assert_eq!(Grid::new_empty(4, 4).live_bounds(), None);
+        let g = Grid::from_ascii("...\n..O\n...", 'O', '.');
+        assert_eq!(g.live_bounds(), Some((Point::new(2, 1), Point::new(2, 1))));
+        let g = Grid::from_ascii("O...\n....\n...O", 'O', '.');
+        assert_eq!(g.live_bounds(), Some((Point::new(0, 0), Point::new(3, 2))));
+    }
+
+    #[test]
+    fn test_crop_to_content() {
+        let mut g = Grid::from_ascii("......\n.O....\n...O..\n......", 'O', '.');
+        g.topology = Topology::Toroidal;
+        let cropped = g.crop_to_content();
+        assert_eq!((cropped.width, cropped.height), (3, 2));
+        assert_eq!(cropped.population, 2);
+        assert_eq!(cropped.topology, Topology::Toroidal);
+        assert_eq!(cropped.to_string(), "O..\n..O\n");
+
+        let empty = Grid::new_empty(5, 5).crop_to_content();
+        assert_eq!((empty.width, empty.height), (0, 0));
+        assert!(empty.cells.is_empty());
+        assert_eq!(empty.population, 0);
+    }
+
+    #[test]
+    fn test_step_back() {
+        let mut g = Grid::from_ascii("......\n..OO..\n.OO...\n..O...\n......", 'O', '.');
+        assert!(!g.step_back());
         g.update_states();
+        assert!(!g.step_back());
+
+        g.history_limit = 2;
+        let mut seen = vec![g.clone()];
+        for _ in 0..3 {
+            g.update_states();
+            seen.push(g.clone());
+        }
+        assert!(g.step_back());
+        assert!(g.same_pattern(&seen[2]));
+        assert!(g.step_back());
+        assert!(g.same_pattern(&seen[1]));
+        assert_eq!(g.population, seen[1].population);
+        // Only the last two generations were kept
+        assert!(!g.step_back());
     }
 
     #[test]
-    fn test_get_cell_state() {
-        let g = Grid::new_empty(3, 3);
-        // Any live cell with 0 or 1 live neighbors becomes dead, because of underpopulation
-        assert_eq!(
-            g.get_cell_state(&CellState::Alive('X'), NeighbourState { alive: 1, dead: 0 }),
-            CellState::Dead(' ')
-        );
-        //Any live cell with 2 or 3 live neighbors stays alive, because its neighborhood is just right
-        assert_eq!(
-            g.get_cell_state(&CellState::Alive('X'), NeighbourState { alive: 3, dead: 0 }),
-            CellState::Alive('X')
-        );
-        // Any live cell with more than 3 live neighbors becomes dead, because of overpopulation
-        assert_eq!(
-            g.get_cell_state(&CellState::Alive('X'), NeighbourState { alive: 5, dead: 1 }),
-            CellState::Dead(' ')
-        );
-        // Any dead cell with exactly 3 live neighbors becomes alive, by reproduction
-        assert_eq!(
-            g.get_cell_state(&CellState::Dead(' '), NeighbourState { alive: 3, dead: 0 }),
-            CellState::Alive('X')
-        );
+    fn test_step_back_ages() {
+        let mut g = Grid::from_ascii("....\n.OO.\n.OO.\n....", 'O', '.');
+        g.history_limit = 5;
+        for _ in 0..3 {
+            g.update_states();
+        }
+        assert_eq!(g.ages()[5], 3);
+        assert!(g.step_back());
+        assert_eq!(g.ages()[5], 2);
     }
 
     #[test]
-    fn test_new_random_custom_glyphs() {
-        let g = Grid::new_random_custom_glyphs(3, 3, 'A', 'D');
-        assert_eq!(g.cells.len(), 9);
-        let unexpected_states: Vec<&CellState> = g
-            .cells
-            .iter()
-            .filter(|c| {
-                let state = c.to_string();
-                state != "A" && state != "D"
-            })
-            .collect();
-        assert_eq!(unexpected_states.len(), 0);
+    fn test_analyze_motion_keeps_history() {
+        let mut g = Grid::from_ascii("...\nOOO\n...", 'O', '.');
+        g.history_limit = 10;
+        g.update_states();
+        g.analyze_motion(4, 0);
+        assert!(g.step_back());
+        assert!(!g.step_back());
+    }
+
+    #[test]
+    fn test_activity_map() {
+        let mut g = Grid::from_ascii("....\n.OO.\n.O..\n....", 'O', '.');
+        assert_eq!(g.activity_map(), &[0; 16]);
+        // The missing corner of the block is born, then the block is still
+        g.update_states();
+        g.update_states();
+        g.update_states();
+        let mut expected = [0; 16];
+        expected[10] = 1;
+        assert_eq!(g.activity_map(), &expected);
+
+        g.reset_activity();
+        assert_eq!(g.activity_map(), &[0; 16]);
+    }
+
+    #[test]
+    fn test_index_tuple() {
+        let mut g = Grid::from_ascii("O..\n...", 'O', '.');
+        assert!(g[(0, 0)].is_alive());
+        assert!(g[(2, 1)].is_dead());
+        g[(2, 1)] = CellState::Alive('O');
+        assert_eq!(g.cells[5], CellState::Alive('O'));
+        assert_eq!(g.population, 1);
+    }
+
+    #[test]
+    #[should_panic(expected = "outside a 3x2 grid")]
+    fn test_index_tuple_out_of_bounds() {
+        let g = Grid::new_empty(3, 2);
+        // Would wrap onto the next row if the column was not checked
+        let _ = g[(3, 0)];
+    }
+
+    #[test]
+    fn test_population_ignores_glyphs() {
+        let mut g = Grid::new_empty_custom_glyphs(4, 4, 'X', '.');
+        for (idx, glyph) in [(0, 'X'), (3, 'A'), (5, 'B'), (10, '🦠'), (15, ' ')] {
+            g.cells[idx] = CellState::Alive(glyph);
+        }
+        g.cells[6] = CellState::Dying('X', 1);
+        g.cells[7] = CellState::Dead('X');
+        assert_eq!(g.calculate_population(), 5);
+
+        // A block of four different glyphs is still four live cells after a step
+        let mut g = Grid::new_empty(4, 4);
+        for (idx, glyph) in [(5, 'A'), (6, 'B'), (9, 'C'), (10, 'D')] {
+            g.cells[idx] = CellState::Alive(glyph);
+        }
+        g.color_mode = ColorMode::Immigration;
+        g.update_states();
+        assert_eq!(g.population, 4);
+        assert_eq!(g.live_points().len(), 4);
+    }
+
+    #[test]
+    fn test_set_glyphs() {
+        let mut g = Grid::from_ascii("O..\n.OO", 'O', '.');
+        g.cells[1] = CellState::Alive('B');
+        g.cells[2] = CellState::Dying('+', 1);
+        g.set_alive_glyph('█');
+        g.set_dead_glyph(' ');
+        assert_eq!(g.alive_glyph, '█');
+        assert_eq!(g.dead_glyph, ' ');
+        assert_eq!(g.to_string(), "█B+\n ██\n");
+        assert_eq!(g.population, 4);
+
+        g.update_states();
+        assert_eq!(g.population, g.calculate_population());
+        assert!(g.cells.iter().all(|c| match c {
+            CellState::Alive(glyph) => *glyph == '█',
+            CellState::Dead(glyph) => *glyph == ' ',
+            CellState::Dying(..) => true,
+        }));
+    }
+
+    #[test]
+    fn test_toggle() {
+        let mut g = Grid::new_empty_custom_glyphs(3, 2, '#', '.');
+        assert_eq!(g.toggle(2, 1), Some(CellState::Alive('#')));
+        assert_eq!(g.population, 1);
+        assert_eq!(g.toggle(2, 1), Some(CellState::Dead('.')));
+        assert_eq!(g.population, 0);
+        assert_eq!(g.cells[5], CellState::Dead('.'));
+
+        g.cells[0] = CellState::Dying('+', 1);
+        assert_eq!(g.toggle(0, 0), Some(CellState::Alive('#')));
+        assert_eq!(g.toggle(0, 2), None);
+        assert_eq!(g.toggle(3, 0), None);
     }
 }
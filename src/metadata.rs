@@ -0,0 +1,77 @@
+//! Names, authors and comments carried by pattern files
+
+use alloc::{string::String, vec::Vec};
+
+#[derive(PartialEq, Eq, Clone, Debug, Default)]
+/// `PatternMetadata` holds the descriptive lines of a pattern file. It is filled in by
+/// `Grid::from_plaintext` and `Grid::from_rle_reader` and written back out by
+/// `Grid::to_plaintext`, so attribution survives loading and saving a pattern.
+pub struct PatternMetadata {
+    /// Name of the pattern, from a plaintext `!Name:` line or an RLE `#N` line
+    pub name: Option<String>,
+    /// Who found the pattern, from a plaintext `!Author:` line or an RLE `#O` line
+    pub author: Option<String>,
+    /// Every other comment, in the order they appear. RLE `#C` and `#c` lines keep only their
+    /// text while any other `#` line is kept whole.
+    pub comments: Vec<String>,
+}
+
+impl PatternMetadata {
+    /// Records a plaintext comment, given without its leading `!`
+    pub(crate) fn add_plaintext(&mut self, comment: &str) {
+        if let Some(name) = comment.strip_prefix("Name:") {
+            self.name = Some(name.trim().into());
+        } else if let Some(author) = comment.strip_prefix("Author:") {
+            self.author = Some(author.trim().into());
+        } else {
+            self.comments.push(comment.trim().into());
+        }
+    }
+
+    /// Records an RLE `#` line
+    #[cfg(feature = "std")]
+    pub(crate) fn add_rle(&mut self, line: &str) {
+        let text = line.get(2..).unwrap_or_default().trim();
+        match line.get(..2) {
+            Some("#N") => self.name = Some(text.into()),
+            Some("#O") => self.author = Some(text.into()),
+            Some("#C" | "#c") => self.comments.push(text.into()),
+            _ => self.comments.push(line.into()),
+        }
+    }
+
+    /// Writes the metadata as plaintext `!` comment lines
+    pub(crate) fn write_plaintext(&self, out: &mut String) {
+        let name = self.name.iter().map(|name| ("Name: ", name));
+        let author = self.author.iter().map(|author| ("Author: ", author));
+        let comments = self.comments.iter().map(|comment| ("", comment));
+        for (label, text) in name.chain(author).chain(comments) {
+            out.push('!');
+            out.push_str(label);
+            out.push_str(text);
+            out.push('\n');
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_add_rle() {
+        let mut metadata = PatternMetadata::default();
+        for line in [
+            "#N Glider",
+            "#O Richard K. Guy",
+            "#C The smallest",
+            "#c spaceship",
+            "#P 1 1",
+        ] {
+            metadata.add_rle(line);
+        }
+        assert_eq!(metadata.name.as_deref(), Some("Glider"));
+        assert_eq!(metadata.author.as_deref(), Some("Richard K. Guy"));
+        assert_eq!(metadata.comments, ["The smallest", "spaceship", "#P 1 1"]);
+    }
+}
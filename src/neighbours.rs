@@ -0,0 +1,113 @@
+//! Precomputed neighbour lookups for the cells on the edges of a grid
+
+use alloc::vec::Vec;
+
+use smallvec::SmallVec;
+
+use crate::{CellState, Coord, NeighbourState, Point, Topology};
+
+#[derive(Clone, Default)]
+/// Lists the indices of the neighbours of every cell on the edge of a `width` by `height` grid
+/// under `topology`, so stepping does no bounds checks or coordinate maths for those cells.
+/// Neighbours that do not exist, such as those beyond a bounded edge, are left out.
+///
+/// Interior cells always have all eight neighbours at fixed offsets so they are not stored,
+/// which keeps the table small and leaves the interior reading only `cells`.
+pub(crate) struct NeighbourTable {
+    width: usize,
+    height: usize,
+    topology: Topology,
+    /// Neighbours of each edge cell, in increasing order of cell index
+    edges: Vec<SmallVec<[usize; 8]>>,
+}
+
+impl NeighbourTable {
+    /// Rebuilds the table if it was built for a different size or topology
+    pub(crate) fn refresh(&mut self, width: usize, height: usize, topology: Topology) {
+        if (self.width, self.height, self.topology) == (width, height, topology) {
+            return;
+        }
+        self.width = width;
+        self.height = height;
+        self.topology = topology;
+        self.edges = (0..width * height)
+            .map(|idx| Point::new((idx % width) as Coord, (idx / width) as Coord))
+            .filter(|p| {
+                let (x, y) = (p.x as usize, p.y as usize);
+                x == 0 || y == 0 || x + 1 == width || y + 1 == height
+            })
+            .map(|point| {
+                Point::DIRECTIONS
+                    .into_iter()
+                    .filter_map(|dir| topology.resolve(point + dir, width, height))
+                    .map(|p| p.y as usize * width + p.x as usize)
+                    .collect()
+            })
+            .collect();
+    }
+
+    /// Counts the live and dead neighbours of the `edge`th edge cell in index order, where
+    /// `outside_alive` is whether the cells beyond a bounded edge are alive
+    #[inline]
+    pub(crate) fn state(
+        &self,
+        edge: usize,
+        cells: &[CellState],
+        outside_alive: bool,
+    ) -> NeighbourState {
+        let neighbours = &self.edges[edge];
+        let mut alive = neighbours.iter().filter(|&&n| cells[n].is_alive()).count() as i32;
+        if outside_alive {
+            alive += 8 - neighbours.len() as i32;
+        }
+        NeighbourState {
+            alive,
+            dead: 8 - alive,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn test_table_matches_neighbours_state() {
+        let mut g = Grid::from_ascii("O..O.\n.OO..\nO...O\n..O.O", 'O', '.');
+        for topology in [
+            Topology::Bounded,
+            Topology::BoundedAlive,
+            Topology::Toroidal,
+            Topology::Cylinder {
+                wrap_x: true,
+                wrap_y: false,
+            },
+        ] {
+            g.topology = topology;
+            let mut table = NeighbourTable::default();
+            table.refresh(g.width, g.height, g.topology);
+            // Every cell but the middle three of the second and third rows
+            let edges = (0..g.cells.len()).filter(|idx| ![6, 7, 8, 11, 12, 13].contains(idx));
+            for (edge, idx) in edges.enumerate() {
+                assert_eq!(
+                    table.state(edge, &g.cells, g.outside_cell().is_alive()),
+                    g.get_neighbours_state(g.pos(idx)),
+                    "{topology:?} cell {idx}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_table_rebuilt_on_resize() {
+        let mut table = NeighbourTable::default();
+        table.refresh(3, 3, Topology::Bounded);
+        assert_eq!(table.edges.len(), 8);
+        table.refresh(2, 2, Topology::Bounded);
+        assert_eq!(table.edges.len(), 4);
+        assert_eq!(table.edges[0].as_slice(), [1, 3, 2]);
+        table.refresh(2, 2, Topology::Toroidal);
+        assert_eq!(table.edges[0].len(), 8);
+    }
+}
@@ -0,0 +1,68 @@
+//! Well known patterns ready to run
+
+use crate::{CellState, Grid, Point, Ruleset};
+
+/// Bill Gosper's glider gun, which fires a new glider towards the bottom right every 30
+/// generations
+const GOSPER_GLIDER_GUN: &str = "\
+........................O...........
+......................O.O...........
+............OO......OO............OO
+...........O...O....OO............OO
+OO........O.....O...OO..............
+OO........O...O.OO....O.O...........
+..........O.....O.......O...........
+...........O...O....................
+............OO......................";
+
+/// Width and height of the grid made by `gosper_glider_gun`
+const GUN_GRID_SIZE: usize = 60;
+
+/// A `Grid` holding a Gosper glider gun in the top left corner, with Conway's B3/S23 rules.
+///
+/// The grid is 60 by 60 cells so the gun has room to fire gliders down and to the right for
+/// a while, although they eventually crash into the bottom edge as the grid is bounded.
+/// ```
+/// use gridlife::patterns::gosper_glider_gun;
+/// let mut grid = gosper_glider_gun();
+/// assert_eq!(grid.population, 36);
+/// for _ in 0..30 {
+///     grid.update_states();
+/// }
+/// // The gun is back where it started, plus one glider
+/// assert_eq!(grid.population, 41);
+/// ```
+pub fn gosper_glider_gun() -> Grid<CellState> {
+    let mut grid = Grid::new_empty_with_rules(GUN_GRID_SIZE, GUN_GRID_SIZE, Ruleset::conway());
+    for (y, row) in GOSPER_GLIDER_GUN.lines().enumerate() {
+        for (x, c) in row.chars().enumerate() {
+            if c == 'O' {
+                // One cell in from the edges so the gun is not cut off by them
+                grid.set(Point::new(x as i32 + 1, y as i32 + 1), true);
+            }
+        }
+    }
+    grid
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gosper_glider_gun_fires() {
+        let mut grid = gosper_glider_gun();
+        assert!(grid.width >= 40 && grid.height >= 40);
+        assert_eq!(grid.rules, Ruleset::conway());
+        let start = grid.population;
+        let mut populations = vec![start];
+        for _ in 0..90 {
+            grid.update_states();
+            populations.push(grid.population);
+        }
+        // Each period of 30 generations adds a glider of 5 cells
+        assert_eq!(populations[30], start + 5);
+        assert_eq!(populations[60], start + 10);
+        assert_eq!(populations[90], start + 15);
+    }
+}
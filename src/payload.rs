@@ -0,0 +1,142 @@
+//! Attaching arbitrary data to live cells
+
+use alloc::{vec, vec::Vec};
+
+use crate::{color::majority, CellState, Grid, Point};
+
+#[derive(Debug, Clone)]
+/// `PayloadGrid` pairs a `Grid` with a value of `M` for every live cell, such as a colour or an
+/// owner id, which is carried along as the grid evolves.
+///
+/// A payload lasts as long as its cell stays alive and is dropped when the cell dies. A newborn
+/// cell inherits the most common payload among its live neighbours in the previous generation,
+/// with ties going to the neighbour found first clockwise from north. Neighbours without a
+/// payload are ignored, so a cell born only from such neighbours has no payload either.
+/// ```
+/// use gridlife::{Grid, PayloadGrid, Point};
+/// let mut grid = PayloadGrid::new(Grid::new_empty(5, 5));
+/// grid.set(Point::new(1, 2), Some("red"));
+/// grid.set(Point::new(2, 2), Some("blue"));
+/// grid.set(Point::new(3, 2), Some("red"));
+/// grid.step();
+/// assert_eq!(grid.payload(Point::new(2, 1)), Some(&"red"));
+/// assert_eq!(grid.payload(Point::new(2, 2)), Some(&"blue"));
+/// ```
+pub struct PayloadGrid<M> {
+    grid: Grid<CellState>,
+    payload: Vec<Option<M>>,
+}
+
+impl<M: Clone + PartialEq> PayloadGrid<M> {
+    /// Create a `PayloadGrid` from `grid`, with no payload on any of its cells
+    pub fn new(grid: Grid<CellState>) -> Self {
+        let payload = vec![None; grid.cells.len()];
+        PayloadGrid { grid, payload }
+    }
+
+    /// The grid the payloads are attached to
+    pub fn grid(&self) -> &Grid<CellState> {
+        &self.grid
+    }
+
+    /// Returns the grid, discarding the payloads
+    pub fn into_grid(self) -> Grid<CellState> {
+        self.grid
+    }
+
+    /// Returns the payload of the cell at `point`, or `None` if the cell has no payload or
+    /// `point` is outside the grid
+    pub fn payload(&self, point: Point) -> Option<&M> {
+        if !self.grid.contains(&point) {
+            return None;
+        }
+        self.payload[self.grid.idx(&point)].as_ref()
+    }
+
+    /// Sets the cell at `point` alive carrying `payload`, or dead if `payload` is `None`.
+    /// Returns the previous state of the cell, or `None` if `point` is outside the grid.
+    pub fn set(&mut self, point: Point, payload: Option<M>) -> Option<CellState> {
+        let previous = self.grid.set(point, payload.is_some())?;
+        let idx = self.grid.idx(&point);
+        self.payload[idx] = payload;
+        Some(previous)
+    }
+
+    /// Advance one generation, carrying payloads over to surviving and newborn cells
+    pub fn step(&mut self) {
+        let (next, population) = self.grid.next_cells();
+        let payload = (0..next.len())
+            .map(|idx| {
+                let was_alive = self.grid.cells[idx].is_alive();
+                match (was_alive, next[idx].is_alive()) {
+                    (true, true) => self.payload[idx].clone(),
+                    (false, true) => self.inherited(idx),
+                    _ => None,
+                }
+            })
+            .collect();
+        self.grid.apply_next(next, population);
+        self.payload = payload;
+    }
+
+    /// The most common payload among the live neighbours of the cell at `idx`
+    fn inherited(&self, idx: usize) -> Option<M> {
+        let grid = &self.grid;
+        let point = grid.pos(idx);
+        let parents = Point::DIRECTIONS.into_iter().filter_map(|dir| {
+            let neighbour = grid
+                .topology
+                .resolve(point + dir, grid.width, grid.height)?;
+            let neighbour = grid.idx(&neighbour);
+            if grid.cells[neighbour].is_alive() {
+                self.payload[neighbour].as_ref()
+            } else {
+                None
+            }
+        });
+        majority(parents).cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_payload_survives_and_dies() {
+        // A block keeps its payloads, while a lone cell dies and loses its payload
+        let mut g = PayloadGrid::new(Grid::new_empty(6, 4));
+        for (x, y, id) in [(0, 0, 1), (1, 0, 2), (0, 1, 3), (1, 1, 4), (4, 2, 5)] {
+            g.set(Point::new(x, y), Some(id));
+        }
+        g.step();
+        assert_eq!(g.payload(Point::new(1, 1)), Some(&4));
+        assert_eq!(g.payload(Point::new(4, 2)), None);
+        assert_eq!(g.grid().population, 4);
+    }
+
+    #[test]
+    fn test_payload_inherits_majority() {
+        // .A.
+        // B.B
+        // The centre is born with the payload held by two of its three parents
+        let mut g = PayloadGrid::new(Grid::new_empty(3, 3));
+        g.set(Point::new(1, 0), Some('A'));
+        g.set(Point::new(0, 1), Some('B'));
+        g.set(Point::new(2, 1), Some('B'));
+        g.step();
+        assert_eq!(g.payload(Point::new(1, 1)), Some(&'B'));
+    }
+
+    #[test]
+    fn test_payload_tie_favours_first_clockwise() {
+        let mut g = PayloadGrid::new(Grid::new_empty(3, 3));
+        g.set(Point::new(1, 0), Some('N'));
+        g.set(Point::new(2, 1), Some('E'));
+        // A live parent without a payload is ignored
+        g.grid.set(Point::new(1, 2), true);
+        g.step();
+        assert_eq!(g.payload(Point::new(1, 1)), Some(&'N'));
+        assert_eq!(g.payload(Point::new(9, 9)), None);
+    }
+}
@@ -0,0 +1,131 @@
+//! Support for the plaintext `.cells` pattern format
+//!
+//! See: <https://conwaylife.com/wiki/Plaintext>
+
+use alloc::{string::String, vec::Vec};
+
+use crate::{CellState, Grid, ParseError, PatternMetadata};
+
+const ALIVE: char = 'O';
+const DEAD: char = '.';
+const COMMENT: char = '!';
+
+impl Grid<CellState> {
+    /// Parses a pattern in the plaintext `.cells` format, where `O` is a live cell, `.` is a dead
+    /// cell and lines starting with `!` are comments, kept in the grid's `metadata`.
+    /// The size of the grid is taken from the pattern, with short rows padded with dead cells.
+    /// ```
+    /// use gridlife::Grid;
+    /// let glider = Grid::from_plaintext("!Name: Glider\n.O.\n..O\nOOO\n").unwrap();
+    /// assert_eq!((glider.width, glider.height, glider.population), (3, 3, 5));
+    /// assert_eq!(glider.metadata.name.as_deref(), Some("Glider"));
+    /// ```
+    pub fn from_plaintext(input: &str) -> Result<Self, ParseError> {
+        let mut metadata = PatternMetadata::default();
+        let rows: Vec<(usize, &str)> = input
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| match line.strip_prefix(COMMENT) {
+                Some(comment) => {
+                    metadata.add_plaintext(comment);
+                    false
+                }
+                None => true,
+            })
+            .map(|(idx, line)| (idx + 1, line.trim_end()))
+            .collect();
+        for &(line, row) in &rows {
+            if let Some((column, found)) = row
+                .chars()
+                .enumerate()
+                .find(|&(_, c)| c != ALIVE && c != DEAD)
+            {
+                return Err(ParseError::InvalidChar {
+                    line,
+                    column: column + 1,
+                    found,
+                });
+            }
+        }
+        let width = rows.iter().map(|(_, row)| row.len()).max().unwrap_or(0);
+        if width == 0 {
+            return Err(ParseError::Empty);
+        }
+        let mut grid = Self::new_empty(width, rows.len());
+        for (y, (_, row)) in rows.iter().enumerate() {
+            for (x, c) in row.chars().enumerate() {
+                if c == ALIVE {
+                    grid.cells[y * width + x] = CellState::Alive(grid.alive_glyph);
+                }
+            }
+        }
+        grid.population = grid.calculate_population();
+        grid.metadata = metadata;
+        Ok(grid)
+    }
+
+    /// Writes the grid in the plaintext `.cells` format, using `O` for live cells and `.` for
+    /// dead cells regardless of the grid's glyphs. Any `metadata` is written first as comments.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::from_ascii("X \n X", 'X', ' ');
+    /// assert_eq!(grid.to_plaintext(), "O.\n.O\n");
+    /// grid.metadata.name = Some("Diagonal".to_string());
+    /// assert_eq!(grid.to_plaintext(), "!Name: Diagonal\nO.\n.O\n");
+    /// ```
+    pub fn to_plaintext(&self) -> String {
+        let mut out = String::with_capacity((self.width + 1) * self.height);
+        self.metadata.write_plaintext(&mut out);
+        for row in self.cells.chunks(self.width.max(1)) {
+            out.extend(row.iter().map(|c| if c.is_alive() { ALIVE } else { DEAD }));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_from_plaintext() {
+        let g = Grid::from_plaintext("!Name: Glider\n!\n.O\n..O\nOOO\n").unwrap();
+        assert_eq!((g.width, g.height), (3, 3));
+        assert_eq!(g.population, 5);
+        assert_eq!(g.to_string(), " X \n  X\nXXX\n");
+    }
+
+    #[test]
+    fn test_from_plaintext_errors() {
+        assert_eq!(
+            Grid::from_plaintext("!comment\n").unwrap_err(),
+            ParseError::Empty
+        );
+        assert_eq!(Grid::from_plaintext("").unwrap_err(), ParseError::Empty);
+        assert_eq!(
+            Grid::from_plaintext("!x\n.O\nO*").unwrap_err(),
+            ParseError::InvalidChar {
+                line: 3,
+                column: 2,
+                found: '*'
+            }
+        );
+    }
+
+    #[test]
+    fn test_plaintext_metadata_round_trip() {
+        let text = "!Name: Blinker\n!Author: John Conway\n!\n!Period 2\nOOO\n";
+        let g = Grid::from_plaintext(text).unwrap();
+        assert_eq!(g.metadata.author.as_deref(), Some("John Conway"));
+        assert_eq!(g.metadata.comments, ["", "Period 2"]);
+        assert_eq!(g.to_plaintext(), text);
+    }
+
+    #[test]
+    fn test_plaintext_round_trip() {
+        let text = "..O.\nO...\n....\n.OO.\n";
+        let g = Grid::from_plaintext(text).unwrap();
+        assert_eq!(g.to_plaintext(), text);
+    }
+}
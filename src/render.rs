@@ -0,0 +1,126 @@
+//! Rendering grids to images
+
+#[cfg(feature = "gif")]
+use std::io::{self, Write};
+
+#[cfg(feature = "gif")]
+use image::{
+    codecs::gif::{GifEncoder, Repeat},
+    Delay, Frame,
+};
+use image::{Rgba, RgbaImage};
+
+use crate::{CellState, Grid};
+
+/// Default colour of live cells in `Grid::to_image`
+pub const ALIVE_COLOR: Rgba<u8> = Rgba([0, 0, 0, 255]);
+/// Default colour of dead cells in `Grid::to_image`
+pub const DEAD_COLOR: Rgba<u8> = Rgba([255, 255, 255, 255]);
+
+impl Grid<CellState> {
+    /// Draws the grid as an image, with each cell a square of `cell_px` pixels.
+    /// Live cells are black and dead cells are white.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::new_random(4, 3);
+    /// let image = grid.to_image(10);
+    /// assert_eq!(image.dimensions(), (40, 30));
+    /// ```
+    pub fn to_image(&self, cell_px: u32) -> RgbaImage {
+        self.to_image_with_colors(cell_px, ALIVE_COLOR, DEAD_COLOR)
+    }
+
+    /// Draws the grid as an image like `to_image`, using `alive` and `dead` as the cell colours
+    pub fn to_image_with_colors(&self, cell_px: u32, alive: Rgba<u8>, dead: Rgba<u8>) -> RgbaImage {
+        let cell_px = cell_px.max(1);
+        RgbaImage::from_fn(
+            self.width as u32 * cell_px,
+            self.height as u32 * cell_px,
+            |x, y| {
+                let idx = (y / cell_px) as usize * self.width + (x / cell_px) as usize;
+                if self.cells[idx].is_alive() {
+                    alive
+                } else {
+                    dead
+                }
+            },
+        )
+    }
+
+    /// Records an animated GIF of the simulation, encoding the current generation as the first
+    /// frame and stepping the grid after each of the `frames` frames.
+    /// Each cell is a square of `cell_px` pixels and each frame is shown for `delay_ms`
+    /// milliseconds. The animation loops forever.
+    /// ```
+    /// use gridlife::Grid;
+    /// let mut grid = Grid::new_random(8, 8);
+    /// let mut gif = Vec::new();
+    /// grid.record_gif(10, 4, 100, &mut gif).unwrap();
+    /// ```
+    #[cfg(feature = "gif")]
+    pub fn record_gif(
+        &mut self,
+        frames: usize,
+        cell_px: u32,
+        delay_ms: u16,
+        out: impl Write,
+    ) -> io::Result<()> {
+        let mut encoder = GifEncoder::new(out);
+        encoder.set_repeat(Repeat::Infinite).map_err(to_io_error)?;
+        let delay = Delay::from_numer_denom_ms(u32::from(delay_ms), 1);
+        for _ in 0..frames {
+            let frame = Frame::from_parts(self.to_image(cell_px), 0, 0, delay);
+            encoder.encode_frame(frame).map_err(to_io_error)?;
+            self.update_states();
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "gif")]
+fn to_io_error(e: image::ImageError) -> io::Error {
+    match e {
+        image::ImageError::IoError(e) => e,
+        e => io::Error::new(io::ErrorKind::Other, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_to_image_dimensions() {
+        let g = Grid::new_random(7, 5);
+        let image = g.to_image(3);
+        assert_eq!(image.dimensions(), (21, 15));
+    }
+
+    #[test]
+    fn test_to_image_colors() {
+        let g = Grid::from_ascii("O.", 'O', '.');
+        let red = Rgba([255, 0, 0, 255]);
+        let blue = Rgba([0, 0, 255, 255]);
+        let image = g.to_image_with_colors(2, red, blue);
+        assert_eq!(image.get_pixel(1, 1), &red);
+        assert_eq!(image.get_pixel(2, 0), &blue);
+        assert_eq!(g.to_image(1).get_pixel(0, 0), &ALIVE_COLOR);
+    }
+
+    #[cfg(feature = "gif")]
+    #[test]
+    fn test_record_gif() {
+        use image::{codecs::gif::GifDecoder, AnimationDecoder};
+
+        let mut g = Grid::from_ascii(".....\n.....\n.OOO.\n.....\n.....", 'O', '.');
+        let mut gif = Vec::new();
+        g.record_gif(3, 2, 50, &mut gif).unwrap();
+        assert!(gif.starts_with(b"GIF89a"));
+        let decoder = GifDecoder::new(std::io::Cursor::new(gif)).unwrap();
+        let frames = decoder.into_frames().collect_frames().unwrap();
+        assert_eq!(frames.len(), 3);
+        assert_eq!(frames[0].buffer().dimensions(), (10, 10));
+        // Blinker has been stepped once per frame
+        assert_eq!(g.to_string(), ".....\n..O..\n..O..\n..O..\n.....\n");
+    }
+}
@@ -0,0 +1,138 @@
+//! Reversible second-order variant of the game of life
+
+use crate::{CellState, Grid};
+
+#[derive(Debug, Clone)]
+/// `SecondOrderGrid` runs Conways game of life as a reversible second-order automaton.
+///
+/// Each generation is computed as the usual next state of the current generation XOR the
+/// previous generation. Because XOR is its own inverse, the previous generation can be
+/// recovered exactly from the current and next ones, so the automaton can be stepped backwards
+/// without storing any history.
+pub struct SecondOrderGrid {
+    previous: Grid<CellState>,
+    current: Grid<CellState>,
+    generation: u64,
+}
+
+impl SecondOrderGrid {
+    /// Create a `SecondOrderGrid` starting at generation `0` from `grid`, with an all-dead
+    /// previous generation
+    /// ```
+    /// use gridlife::{Grid, SecondOrderGrid};
+    /// let grid = SecondOrderGrid::new(Grid::new_random(5, 5));
+    /// assert_eq!(grid.generation(), 0);
+    /// ```
+    pub fn new(grid: Grid<CellState>) -> Self {
+        let previous =
+            Grid::empty_with_glyphs(grid.width, grid.height, grid.alive_glyph, grid.dead_glyph);
+        SecondOrderGrid {
+            previous,
+            current: grid,
+            generation: 0,
+        }
+    }
+
+    /// The current generation of the grid
+    pub fn current(&self) -> &Grid<CellState> {
+        &self.current
+    }
+
+    /// Number of generations stepped forward from the starting grid
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Advance one generation
+    pub fn step(&mut self) {
+        let next = Self::combine(&self.current, &self.previous);
+        self.previous = core::mem::replace(&mut self.current, next);
+        self.generation += 1;
+    }
+
+    /// Go back one generation, returning `false` if already at generation `0`
+    pub fn step_back(&mut self) -> bool {
+        if self.generation == 0 {
+            return false;
+        }
+        let before = Self::combine(&self.previous, &self.current);
+        self.current = core::mem::replace(&mut self.previous, before);
+        self.generation -= 1;
+        true
+    }
+
+    /// Step backwards until reaching `generation`.
+    /// Does nothing if `generation` is not earlier than the current generation.
+    /// ```
+    /// use gridlife::{Grid, SecondOrderGrid};
+    /// let mut grid = SecondOrderGrid::new(Grid::new_random(5, 5));
+    /// grid.step();
+    /// grid.step();
+    /// grid.rewind_to(0);
+    /// assert_eq!(grid.generation(), 0);
+    /// ```
+    pub fn rewind_to(&mut self, generation: u64) {
+        while self.generation > generation {
+            self.step_back();
+        }
+    }
+
+    /// Applies the rules to `grid` and XORs the result with `other`
+    fn combine(grid: &Grid<CellState>, other: &Grid<CellState>) -> Grid<CellState> {
+        let mut next = grid.clone();
+        next.update_states();
+        for (idx, cell) in next.cells.iter_mut().enumerate() {
+            *cell = if cell.is_alive() != other.cells[idx].is_alive() {
+                CellState::Alive(grid.alive_glyph)
+            } else {
+                next.age[idx] = 0;
+                CellState::Dead(grid.dead_glyph)
+            };
+        }
+        next.population = next.calculate_population();
+        next
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seeded_grid() -> Grid<CellState> {
+        let mut rng = fastrand::Rng::with_seed(3);
+        let mut g = Grid::new_empty(12, 12);
+        for cell in g.cells.iter_mut() {
+            if rng.bool() {
+                *cell = CellState::Alive('X');
+            }
+        }
+        g.population = g.calculate_population();
+        g
+    }
+
+    #[test]
+    fn test_rewind_to() {
+        let mut grid = SecondOrderGrid::new(seeded_grid());
+        for _ in 0..10 {
+            grid.step();
+        }
+        grid.rewind_to(3);
+
+        let mut fresh = SecondOrderGrid::new(seeded_grid());
+        for _ in 0..3 {
+            fresh.step();
+        }
+        assert_eq!(grid.generation(), 3);
+        assert_eq!(grid.current().cells, fresh.current().cells);
+        assert_eq!(grid.current().population, fresh.current().population);
+    }
+
+    #[test]
+    fn test_step_back_at_start() {
+        let mut grid = SecondOrderGrid::new(seeded_grid());
+        assert!(!grid.step_back());
+        grid.step();
+        assert!(grid.step_back());
+        assert_eq!(grid.current().cells, seeded_grid().cells);
+    }
+}
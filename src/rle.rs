@@ -0,0 +1,275 @@
+//! Support for the run length encoded `.rle` pattern format
+//!
+//! See: <https://conwaylife.com/wiki/Run_Length_Encoded>
+
+use std::io::BufRead;
+
+use crate::{CellState, Grid, PatternMetadata, RleError, Ruleset};
+
+const COMMENT: u8 = b'#';
+
+/// Position in the pattern being decoded, along with any run count read so far.
+/// Kept between chunks of input so that runs can be split across buffer boundaries.
+struct Decoder {
+    x: usize,
+    y: usize,
+    count: Option<usize>,
+    line: usize,
+    column: usize,
+    done: bool,
+}
+
+impl Decoder {
+    fn run(&mut self) -> usize {
+        self.count.take().unwrap_or(1)
+    }
+
+    fn decode(&mut self, grid: &mut Grid<CellState>, byte: u8) -> Result<(), RleError> {
+        self.column += 1;
+        match byte {
+            b'0'..=b'9' => {
+                let digit = usize::from(byte - b'0');
+                let count = self
+                    .count
+                    .unwrap_or(0)
+                    .checked_mul(10)
+                    .and_then(|c| c.checked_add(digit))
+                    .ok_or_else(|| self.out_of_bounds())?;
+                self.count = Some(count);
+            }
+            b'b' | b'.' => {
+                let run = self.run();
+                self.x = self.x.saturating_add(run);
+            }
+            b'o' => {
+                let run = self.run();
+                let end = self.x.saturating_add(run);
+                if end > grid.width || self.y >= grid.height {
+                    return Err(self.out_of_bounds());
+                }
+                let alive = CellState::Alive(grid.alive_glyph);
+                let start = self.y * grid.width;
+                grid.cells[start + self.x..start + end].fill(alive);
+                self.x = end;
+            }
+            b'$' => {
+                let run = self.run();
+                self.y = self.y.saturating_add(run);
+                self.x = 0;
+            }
+            b'!' => self.done = true,
+            b'\n' => {
+                self.line += 1;
+                self.column = 0;
+            }
+            b if b.is_ascii_whitespace() => {}
+            found => {
+                return Err(RleError::InvalidChar {
+                    line: self.line,
+                    column: self.column,
+                    found: char::from(found),
+                })
+            }
+        }
+        Ok(())
+    }
+
+    fn out_of_bounds(&self) -> RleError {
+        RleError::OutOfBounds {
+            line: self.line,
+            column: self.column,
+        }
+    }
+}
+
+/// The fields of a header line such as `x = 3, y = 3, rule = B3/S23`
+struct Header<'a> {
+    width: usize,
+    height: usize,
+    rule: Option<&'a str>,
+}
+
+/// Reads the `x` and `y` dimensions and the `rule` if there is one from a header line.
+/// Other keys are ignored.
+fn parse_header(header: &str) -> Option<Header<'_>> {
+    let mut width = None;
+    let mut height = None;
+    let mut rule = None;
+    for field in header.split(',') {
+        let (key, value) = field.split_once('=')?;
+        match key.trim() {
+            "x" => width = Some(value.trim().parse().ok()?),
+            "y" => height = Some(value.trim().parse().ok()?),
+            "rule" => rule = Some(value.trim()),
+            _ => {}
+        }
+    }
+    Some(Header {
+        width: width?,
+        height: height?,
+        rule,
+    })
+}
+
+impl Grid<CellState> {
+    /// Parses a pattern in the run length encoded `.rle` format from `reader`.
+    ///
+    /// The header line gives the size of the grid, which is allocated up front, and optionally
+    /// the `rule` in `B/S` notation that the grid will use, defaulting to `B3/S23`. The pattern
+    /// is then decoded straight into the grid a buffer at a time, so even very large files are
+    /// never held in memory as text. Lines starting with `#` before the header are comments,
+    /// which are kept in the grid's `metadata`.
+    /// ```
+    /// use gridlife::Grid;
+    /// let rle = "#N Glider\nx = 3, y = 3, rule = B3/S23\nbo$2bo$3o!\n";
+    /// let glider = Grid::from_rle_reader(rle.as_bytes()).unwrap();
+    /// assert_eq!(glider.to_plaintext(), "!Name: Glider\n.O.\n..O\nOOO\n");
+    /// ```
+    pub fn from_rle_reader(mut reader: impl BufRead) -> Result<Self, RleError> {
+        let mut line = String::new();
+        let mut line_number = 0;
+        let mut metadata = PatternMetadata::default();
+        let (width, height, rules) = loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(RleError::MissingHeader);
+            }
+            line_number += 1;
+            let trimmed = line.trim();
+            if trimmed.is_empty() {
+                continue;
+            }
+            if trimmed.as_bytes()[0] == COMMENT {
+                metadata.add_rle(trimmed);
+                continue;
+            }
+            let header =
+                parse_header(trimmed).ok_or(RleError::InvalidHeader { line: line_number })?;
+            let rules = match header.rule {
+                Some(rule) => rule.parse().map_err(|error| RleError::InvalidRule {
+                    line: line_number,
+                    rule: rule.to_string(),
+                    error,
+                })?,
+                None => Ruleset::default(),
+            };
+            break (header.width, header.height, rules);
+        };
+
+        let mut grid = Self::try_new(width, height)?;
+        grid.rules = rules;
+        grid.metadata = metadata;
+        let mut decoder = Decoder {
+            x: 0,
+            y: 0,
+            count: None,
+            line: line_number + 1,
+            column: 0,
+            done: false,
+        };
+        while !decoder.done {
+            let chunk = reader.fill_buf()?;
+            if chunk.is_empty() {
+                break;
+            }
+            let mut used = 0;
+            for &byte in chunk {
+                used += 1;
+                decoder.decode(&mut grid, byte)?;
+                if decoder.done {
+                    break;
+                }
+            }
+            reader.consume(used);
+        }
+        grid.population = grid.calculate_population();
+        Ok(grid)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufReader;
+
+    use super::*;
+    use crate::RuleError;
+
+    #[test]
+    fn test_from_rle_reader() {
+        let rle = "#C A comment\n#N Two gliders\nx = 8, y = 4\nbo4bo$2bo3bo$3o$5b3o!\n";
+        let g = Grid::from_rle_reader(rle.as_bytes()).unwrap();
+        assert_eq!(
+            g.to_plaintext(),
+            "!Name: Two gliders\n!A comment\n.O....O.\n..O...O.\nOOO.....\n.....OOO\n"
+        );
+        assert_eq!(g.population, 10);
+        assert_eq!(g.metadata.name.as_deref(), Some("Two gliders"));
+        assert_eq!(g.metadata.comments, ["A comment"]);
+    }
+
+    #[test]
+    fn test_from_rle_reader_applies_rule() {
+        // The HighLife replicator with room to grow, whose second generation has a birth from
+        // six neighbours in the middle
+        let rle = "x = 7, y = 7, rule = B36/S23\n$3b3o$2bo2bo$bo3bo$bo2bo$b3o!";
+        let mut g = Grid::from_rle_reader(rle.as_bytes()).unwrap();
+        assert_eq!(g.rules, Ruleset::new(&[3, 6], &[2, 3]));
+        let mut conway = g.clone();
+        conway.rules = Ruleset::conway();
+        g.step_n(2);
+        conway.step_n(2);
+        assert!(g.cells[24].is_alive());
+        assert!(conway.cells[24].is_dead());
+
+        let g = Grid::from_rle_reader("x = 1, y = 1\no!".as_bytes()).unwrap();
+        assert_eq!(g.rules, Ruleset::conway());
+    }
+
+    #[test]
+    fn test_from_rle_reader_runs_across_buffers() {
+        // A one byte buffer splits every run count from its tag, and the count "12" in half
+        let rle = "x = 14, y = 2\n12bo$\n1\n4o!";
+        let g = Grid::from_rle_reader(BufReader::with_capacity(1, rle.as_bytes())).unwrap();
+        assert!(g.cells[12].is_alive());
+        assert_eq!(g.population, 15);
+    }
+
+    #[test]
+    fn test_from_rle_reader_stops_at_end_marker() {
+        let g = Grid::from_rle_reader("x = 2, y = 1\n2o!\nthis is ignored".as_bytes()).unwrap();
+        assert_eq!(g.population, 2);
+    }
+
+    #[test]
+    fn test_from_rle_reader_errors() {
+        assert!(matches!(
+            Grid::from_rle_reader("#C only comments\n".as_bytes()),
+            Err(RleError::MissingHeader)
+        ));
+        assert!(matches!(
+            Grid::from_rle_reader("#C\ny = 3\n".as_bytes()),
+            Err(RleError::InvalidHeader { line: 2 })
+        ));
+        assert!(matches!(
+            Grid::from_rle_reader("x = 3, y = 1, rule = B3/S29\n3o!".as_bytes()),
+            Err(RleError::InvalidRule { line: 1, ref rule, error: RuleError::InvalidCount('9') })
+                if rule == "B3/S29"
+        ));
+        assert!(matches!(
+            Grid::from_rle_reader("x = 3, y = 1\n2o\n2bz!".as_bytes()),
+            Err(RleError::InvalidChar {
+                line: 3,
+                column: 3,
+                found: 'z'
+            })
+        ));
+        assert!(matches!(
+            Grid::from_rle_reader("x = 3, y = 1\n4o!".as_bytes()),
+            Err(RleError::OutOfBounds { line: 2, column: 2 })
+        ));
+        assert!(matches!(
+            Grid::from_rle_reader("x = 0, y = 2\n!".as_bytes()),
+            Err(RleError::Grid(_))
+        ));
+    }
+}
@@ -0,0 +1,304 @@
+//! Life-like rules and their analysis
+
+use alloc::vec::Vec;
+use core::{fmt, str::FromStr};
+
+use crate::{CellState, RuleError};
+
+/// Number of neighbours each cell has in the Moore neighbourhood
+const NEIGHBOURS: u32 = 8;
+
+/// Highest live neighbour count a `Ruleset` can hold, enough for a `Grid::range` of `5`
+pub(crate) const MAX_COUNT: u32 = 127;
+
+/// Probability of exactly `k` of the `n` neighbours being alive when each is alive with
+/// probability `p`
+fn binomial(n: u32, k: u32, p: f64) -> f64 {
+    let choose = (0..k).fold(1.0, |acc, i| acc * f64::from(n - i) / f64::from(i + 1));
+    choose * powi(p, k) * powi(1.0 - p, n - k)
+}
+
+/// `x` raised to the power `n`, as `f64::powi` is not available without `std`
+fn powi(x: f64, n: u32) -> f64 {
+    (0..n).fold(1.0, |acc, _| acc * x)
+}
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `Ruleset` describes a life-like rule in `B/S` notation, i.e. the live neighbour counts that
+/// cause a dead cell to be born and a live cell to survive
+pub struct Ruleset {
+    birth: u128,
+    survive: u128,
+}
+
+impl Ruleset {
+    /// Create a `Ruleset` from the neighbour counts that cause a birth and allow survival.
+    /// Counts greater than 8 only occur on grids with a `range` above `1`, and counts greater
+    /// than 127 are ignored.
+    /// ```
+    /// use gridlife::Ruleset;
+    /// // HighLife: B36/S23
+    /// let highlife = Ruleset::new(&[3, 6], &[2, 3]);
+    /// ```
+    pub fn new(birth: &[u8], survive: &[u8]) -> Self {
+        Ruleset {
+            birth: Self::mask(birth),
+            survive: Self::mask(survive),
+        }
+    }
+
+    /// The standard rules of Conways game of life, `B3/S23`
+    pub const fn conway() -> Self {
+        Ruleset {
+            birth: 1 << 3,
+            survive: 1 << 2 | 1 << 3,
+        }
+    }
+
+    fn mask(counts: &[u8]) -> u128 {
+        counts
+            .iter()
+            .filter(|&&n| u32::from(n) <= MAX_COUNT)
+            .fold(0, |mask, &n| mask | 1 << n)
+    }
+
+    /// Whether a dead cell with `alive` live neighbours is born
+    pub fn is_birth(&self, alive: u32) -> bool {
+        alive <= MAX_COUNT && self.birth & (1 << alive) != 0
+    }
+
+    /// Whether a live cell with `alive` live neighbours survives
+    pub fn is_survival(&self, alive: u32) -> bool {
+        alive <= MAX_COUNT && self.survive & (1 << alive) != 0
+    }
+
+    /// Expected fraction of alive cells after a single step of this rule, starting from an
+    /// infinite grid where each cell is independently alive with probability `p`.
+    ///
+    /// Computed analytically from the binomial distribution of live neighbours rather than by
+    /// simulation.
+    pub fn expected_next_density(&self, p: f64) -> f64 {
+        (0..=NEIGHBOURS)
+            .map(|k| {
+                let birth = if self.is_birth(k) { 1.0 - p } else { 0.0 };
+                let survive = if self.is_survival(k) { p } else { 0.0 };
+                (birth + survive) * binomial(NEIGHBOURS, k, p)
+            })
+            .sum()
+    }
+
+    /// Samples `expected_next_density` at `samples` evenly spaced densities across `[0, 1]`,
+    /// returning `(p, next_p)` pairs.
+    ///
+    /// Fixed points of the rule are where the curve crosses the diagonal `next_p == p`.
+    /// ```
+    /// use gridlife::Ruleset;
+    /// let curve = Ruleset::conway().density_curve(11);
+    /// assert_eq!(curve.len(), 11);
+    /// ```
+    pub fn density_curve(&self, samples: usize) -> Vec<(f64, f64)> {
+        let steps = samples.saturating_sub(1).max(1) as f64;
+        (0..samples)
+            .map(|i| {
+                let p = i as f64 / steps;
+                (p, self.expected_next_density(p))
+            })
+            .collect()
+    }
+}
+
+/// `Rule` is a transition function giving the next state of a cell from its current state and
+/// number of live neighbours, for running automata that cannot be written as a `Ruleset`.
+/// Set one on a grid with `Grid::set_rule`.
+///
+/// `Ruleset` implements `Rule`, so `Ruleset::conway()` is the classic Conway rule.
+pub trait Rule: fmt::Debug + Send + Sync {
+    /// The state of a cell in the next generation, where `current` is its state now and
+    /// `live_neighbours` is how many of its neighbours within the grid's `range` are alive
+    fn next(&self, current: CellState, live_neighbours: u32) -> CellState;
+}
+
+impl Rule for Ruleset {
+    /// Live cells survive and dead cells are born according to the `B/S` counts, while dying
+    /// cells always die. Cells that are born are given the default alive glyph `X`.
+    fn next(&self, current: CellState, live_neighbours: u32) -> CellState {
+        match current {
+            CellState::Alive(_) if self.is_survival(live_neighbours) => current,
+            CellState::Dead(_) if self.is_birth(live_neighbours) => CellState::Alive('X'),
+            CellState::Dead(_) => current,
+            CellState::Alive(_) | CellState::Dying(..) => CellState::Dead(' '),
+        }
+    }
+}
+
+impl Default for Ruleset {
+    fn default() -> Self {
+        Self::conway()
+    }
+}
+
+/// Reads the neighbour counts in one half of a rule string into a mask, one digit per count
+fn parse_counts(counts: &str) -> Result<u128, RuleError> {
+    counts.chars().try_fold(0, |mask, c| match c.to_digit(10) {
+        Some(n) if n <= NEIGHBOURS => Ok(mask | 1 << n),
+        _ => Err(RuleError::InvalidCount(c)),
+    })
+}
+
+/// Splits the `B` or `S` off the start of one half of a rule string, if it has one
+fn split_letter(part: &str) -> (Option<char>, &str) {
+    let mut chars = part.chars();
+    match chars.next().map(|c| c.to_ascii_uppercase()) {
+        Some(c @ ('B' | 'S')) => (Some(c), chars.as_str()),
+        _ => (None, part),
+    }
+}
+
+impl FromStr for Ruleset {
+    type Err = RuleError;
+
+    /// Parses a rule in `B/S` notation such as `B36/S23`, in either order and any case, or in
+    /// the older `S/B` notation without letters such as `23/36`
+    ///
+    /// Each count is a single digit, so only counts from `0` to `8` can be written. Rules for a
+    /// `Grid::range` above `1` that need larger counts must be made with `Ruleset::new`.
+    /// ```
+    /// use gridlife::Ruleset;
+    /// let highlife: Ruleset = "B36/S23".parse().unwrap();
+    /// assert_eq!(highlife, Ruleset::new(&[3, 6], &[2, 3]));
+    /// assert_eq!("23/3".parse(), Ok(Ruleset::conway()));
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (first, second) = s.trim().split_once('/').ok_or(RuleError::InvalidFormat)?;
+        let (birth, survive) = match (split_letter(first), split_letter(second)) {
+            ((Some('B'), birth), (Some('S'), survive))
+            | ((Some('S'), survive), (Some('B'), birth))
+            | ((None, survive), (None, birth)) => (birth, survive),
+            _ => return Err(RuleError::InvalidFormat),
+        };
+        Ok(Ruleset {
+            birth: parse_counts(birth)?,
+            survive: parse_counts(survive)?,
+        })
+    }
+}
+
+/// Expected fraction of alive cells after a single step of Conways game of life, starting
+/// from an infinite grid where each cell is independently alive with probability `p`.
+///
+/// Computed analytically from the binomial distribution of live neighbours rather than by
+/// simulation:
+/// - a live cell survives with 2 or 3 live neighbours
+/// - a dead cell is born with exactly 3 live neighbours
+/// ```
+/// use gridlife::expected_next_density;
+/// let next = expected_next_density(0.5);
+/// assert!((next - 0.2734).abs() < 1e-4);
+/// ```
+pub fn expected_next_density(p: f64) -> f64 {
+    Ruleset::conway().expected_next_density(p)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn test_binomial_sums_to_one() {
+        let total: f64 = (0..=NEIGHBOURS).map(|k| binomial(NEIGHBOURS, k, 0.3)).sum();
+        assert!((total - 1.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_expected_next_density_endpoints() {
+        assert_eq!(expected_next_density(0.0), 0.0);
+        assert_eq!(expected_next_density(1.0), 0.0);
+    }
+
+    #[test]
+    fn test_expected_next_density_matches_monte_carlo() {
+        let mut g = Grid::new_random_seeded(400, 400, 1);
+        g.update_states();
+        let simulated = g.population as f64 / (g.width * g.height) as f64;
+        assert!((simulated - expected_next_density(0.5)).abs() < 0.01);
+    }
+
+    #[test]
+    fn test_rule_matches_ruleset() {
+        let mut g = Grid::new_random_seeded(20, 20, 3);
+        let mut custom = g.clone();
+        custom.set_rule(Box::new(Ruleset::conway()));
+        for _ in 0..10 {
+            assert_eq!(custom.update_states(), g.update_states());
+        }
+    }
+
+    #[test]
+    fn test_custom_rule_with_dying_states() {
+        // Brian's Brain, where live cells always spend one generation dying
+        #[derive(Debug)]
+        struct BriansBrain;
+        impl Rule for BriansBrain {
+            fn next(&self, current: CellState, live_neighbours: u32) -> CellState {
+                match current {
+                    CellState::Alive(c) => CellState::Dying(c, 1),
+                    CellState::Dying(c, _) => CellState::Dead(c),
+                    CellState::Dead(c) if live_neighbours == 2 => CellState::Alive(c),
+                    CellState::Dead(_) => current,
+                }
+            }
+        }
+        let mut g = Grid::new_random_seeded(20, 20, 5);
+        g.rules = Ruleset::new(&[2], &[]);
+        g.states = 3;
+        let mut custom = g.clone();
+        custom.set_rule(Box::new(BriansBrain));
+        for _ in 0..10 {
+            assert_eq!(custom.update_states(), g.update_states());
+        }
+        custom.clear_rule();
+        assert_eq!(custom.update_states(), g.update_states());
+    }
+
+    #[test]
+    fn test_ruleset_from_str() {
+        let highlife = Ruleset::new(&[3, 6], &[2, 3]);
+        assert_eq!("B36/S23".parse(), Ok(highlife));
+        assert_eq!("s23/b36".parse(), Ok(highlife));
+        assert_eq!(" 23/36 ".parse(), Ok(highlife));
+        assert_eq!("B/S".parse(), Ok(Ruleset::new(&[], &[])));
+        assert_eq!("B3S23".parse::<Ruleset>(), Err(RuleError::InvalidFormat));
+        assert_eq!("B3/B23".parse::<Ruleset>(), Err(RuleError::InvalidFormat));
+        assert_eq!(
+            "B39/S23".parse::<Ruleset>(),
+            Err(RuleError::InvalidCount('9'))
+        );
+    }
+
+    #[test]
+    fn test_ruleset_new_matches_conway() {
+        assert_eq!(Ruleset::new(&[3], &[2, 3]), Ruleset::conway());
+        assert_eq!(Ruleset::new(&[3, 200], &[3, 2]), Ruleset::default());
+    }
+
+    #[test]
+    fn test_density_curve() {
+        let curve = Ruleset::conway().density_curve(21);
+        assert_eq!(curve.len(), 21);
+        assert_eq!(curve[0], (0.0, 0.0));
+        let (p, next) = curve[20];
+        assert_eq!(p, 1.0);
+        assert!(next.abs() < 1e-12);
+        // Conway rises from zero then collapses again at high densities
+        let rising = curve.windows(2).any(|w| w[1].1 > w[0].1);
+        let falling = curve.windows(2).any(|w| w[1].1 < w[0].1);
+        assert!(rising && falling);
+    }
+
+    #[test]
+    fn test_density_curve_degenerate_samples() {
+        assert!(Ruleset::conway().density_curve(0).is_empty());
+        assert_eq!(Ruleset::conway().density_curve(1), vec![(0.0, 0.0)]);
+    }
+}
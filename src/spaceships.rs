@@ -0,0 +1,122 @@
+//! Finding spaceships among the patterns on a grid
+
+use alloc::vec::Vec;
+
+use crate::{CellState, Coord, Grid, Motion, Point};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+/// `Spaceship` is a pattern found by `Grid::detect_spaceships` that reappears translated after
+/// `period` generations
+pub struct Spaceship {
+    /// Top left corner of the smallest box containing the pattern
+    pub position: Point,
+    /// Number of generations before the shape repeats
+    pub period: usize,
+    /// Horizontal displacement per `period`
+    pub dx: i32,
+    /// Vertical displacement per `period`
+    pub dy: i32,
+}
+
+impl Grid<CellState> {
+    /// Finds the connected components of live cells (see `component_labels`) that move as
+    /// spaceships with a period of up to `max_period` generations, in the order the components
+    /// are labelled.
+    ///
+    /// Each component is evolved on its own with the grid's rules, so components that would
+    /// collide with a neighbour are still reported, and spaceships made of several separate
+    /// components are not found.
+    /// ```
+    /// use gridlife::Grid;
+    /// let grid = Grid::from_ascii("......\n..O...\n...O..\n.OOO..\n......", 'O', '.');
+    /// let ships = grid.detect_spaceships(4);
+    /// assert_eq!(ships.len(), 1);
+    /// assert_eq!((ships[0].period, ships[0].dx, ships[0].dy), (4, 1, 1));
+    /// ```
+    pub fn detect_spaceships(&self, max_period: usize) -> Vec<Spaceship> {
+        let labels = self.component_labels();
+        let mut components: Vec<Vec<Point>> = Vec::new();
+        for (idx, label) in labels.iter().enumerate() {
+            if let Some(label) = *label {
+                if label == components.len() {
+                    components.push(Vec::new());
+                }
+                components[label].push(self.pos(idx));
+            }
+        }
+
+        // A pattern can grow by at most one cell per generation, so this much room on each
+        // side keeps it clear of the edges for a full period
+        let margin = max_period as Coord + 1;
+        components
+            .iter()
+            .filter_map(|points| {
+                let min = Point::new(
+                    points.iter().map(|p| p.x).min()?,
+                    points.iter().map(|p| p.y).min()?,
+                );
+                let max_x = points.iter().map(|p| p.x).max()?;
+                let max_y = points.iter().map(|p| p.y).max()?;
+                let width = (max_x - min.x + 1 + 2 * margin) as usize;
+                let height = (max_y - min.y + 1 + 2 * margin) as usize;
+                let mut isolated =
+                    Self::empty_with_glyphs(width, height, self.alive_glyph, self.dead_glyph);
+                isolated.rules = self.rules;
+                isolated.rule = self.rule.clone();
+                isolated.range = self.range;
+                isolated.states = self.states;
+                for &p in points {
+                    isolated.set(p - min + Point::new(margin, margin), true);
+                }
+                match isolated.analyze_motion(max_period, max_period) {
+                    Motion::Spaceship { period, dx, dy } => Some(Spaceship {
+                        position: min,
+                        period,
+                        dx,
+                        dy,
+                    }),
+                    _ => None,
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{Grid, Point};
+
+    #[test]
+    fn test_detect_spaceships() {
+        // A glider heading south east, a block and a glider heading north west
+        let g = Grid::from_ascii(
+            concat!(
+                "............\n",
+                ".O..........\n",
+                "..O.........\n",
+                "OOO.....OO..\n",
+                "........OO..\n",
+                "............\n",
+                "......OOO...\n",
+                "......O.....\n",
+                ".......O....\n",
+                "............\n",
+            ),
+            'O',
+            '.',
+        );
+        let ships = g.detect_spaceships(4);
+        assert_eq!(ships.len(), 2);
+        assert_eq!(ships[0].position, Point::new(0, 1));
+        assert_eq!((ships[0].period, ships[0].dx, ships[0].dy), (4, 1, 1));
+        assert_eq!(ships[1].position, Point::new(6, 6));
+        assert_eq!((ships[1].period, ships[1].dx, ships[1].dy), (4, -1, -1));
+    }
+
+    #[test]
+    fn test_detect_spaceships_period_too_short() {
+        let g = Grid::from_ascii(".O.\n..O\nOOO", 'O', '.');
+        assert!(g.detect_spaceships(3).is_empty());
+        assert!(Grid::new_empty(4, 4).detect_spaceships(4).is_empty());
+    }
+}
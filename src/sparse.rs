@@ -0,0 +1,186 @@
+//! Unbounded grid storing only the live cells
+
+use std::collections::{HashMap, HashSet};
+
+use crate::{CellState, Grid, Point, Ruleset};
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+/// `SparseGrid` holds the state for a game of life on an unbounded plane.
+///
+/// Only the coordinates of live cells are stored, so patterns can travel arbitrarily far and
+/// memory use depends on the population rather than the area covered.
+///
+/// Every cell that is not stored is dead, so unlike `Grid::background` empty space cannot be
+/// made to start alive, and rules with `B0` would fill the whole plane.
+pub struct SparseGrid {
+    cells: HashSet<(i64, i64)>,
+    rules: Ruleset,
+}
+
+impl SparseGrid {
+    /// Create a new empty `SparseGrid` using the rules of Conways game of life
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create a new `SparseGrid` where the given coordinates are alive
+    /// ```
+    /// use gridlife::SparseGrid;
+    /// let blinker = SparseGrid::from_cells([(0, 0), (1, 0), (2, 0)]);
+    /// assert_eq!(blinker.population(), 3);
+    /// ```
+    pub fn from_cells(cells: impl IntoIterator<Item = (i64, i64)>) -> Self {
+        SparseGrid {
+            cells: cells.into_iter().collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Use `rules` rather than Conways game of life for future updates
+    pub fn with_rules(mut self, rules: Ruleset) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// Copies the live cells of `grid`, placing its top left cell at `origin`
+    pub fn from_grid(grid: &Grid<CellState>, origin: (i64, i64)) -> Self {
+        let cells = grid
+            .cells
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| c.is_alive())
+            .map(|(idx, _)| {
+                let Point { x, y } = grid.pos(idx);
+                (origin.0 + i64::from(x), origin.1 + i64::from(y))
+            });
+        Self::from_cells(cells)
+    }
+
+    /// Renders the window of `width` by `height` cells with its top left cell at `origin` into
+    /// a bounded `Grid`. Live cells outside the window are dropped.
+    /// ```
+    /// use gridlife::SparseGrid;
+    /// let grid = SparseGrid::from_cells([(-1, 0), (5, 5)]).to_grid((-1, 0), 2, 2);
+    /// assert_eq!(grid.population, 1);
+    /// ```
+    pub fn to_grid(&self, origin: (i64, i64), width: usize, height: usize) -> Grid<CellState> {
+        let mut grid = Grid::new_empty(width, height);
+        for &(x, y) in &self.cells {
+            let (dx, dy) = (x - origin.0, y - origin.1);
+            if (0..width as i64).contains(&dx) && (0..height as i64).contains(&dy) {
+                grid.cells[dy as usize * width + dx as usize] = CellState::Alive(grid.alive_glyph);
+                grid.population += 1;
+            }
+        }
+        grid
+    }
+
+    /// Number of live cells
+    pub fn population(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// Whether the cell at `(x, y)` is alive
+    pub fn is_alive(&self, x: i64, y: i64) -> bool {
+        self.cells.contains(&(x, y))
+    }
+
+    /// Sets the cell at `(x, y)` to be alive or dead
+    pub fn set(&mut self, x: i64, y: i64, alive: bool) {
+        if alive {
+            self.cells.insert((x, y));
+        } else {
+            self.cells.remove(&(x, y));
+        }
+    }
+
+    /// Iterates over the coordinates of every live cell, in no particular order
+    pub fn live_cells(&self) -> impl Iterator<Item = (i64, i64)> + '_ {
+        self.cells.iter().copied()
+    }
+
+    /// Re-generates the live cells based on the rules.
+    ///
+    /// Every live cell contributes one to the neighbour count of each of its eight neighbours,
+    /// so only live cells and the cells next to them are ever considered.
+    pub fn update_states(&mut self) {
+        let mut counts: HashMap<(i64, i64), u32> = HashMap::with_capacity(self.cells.len() * 8);
+        for &(x, y) in &self.cells {
+            for d in Point::DIRECTIONS {
+                *counts
+                    .entry((x + i64::from(d.x), y + i64::from(d.y)))
+                    .or_default() += 1;
+            }
+        }
+        let mut next: HashSet<(i64, i64)> = counts
+            .into_iter()
+            .filter(|&(cell, alive)| {
+                if self.cells.contains(&cell) {
+                    self.rules.is_survival(alive)
+                } else {
+                    self.rules.is_birth(alive)
+                }
+            })
+            .map(|(cell, _)| cell)
+            .collect();
+        if self.rules.is_survival(0) {
+            // Cells without any live neighbours never appear in the tally
+            next.extend(self.cells.iter().filter(|&&(x, y)| {
+                Point::DIRECTIONS
+                    .iter()
+                    .all(|d| !self.is_alive(x + i64::from(d.x), y + i64::from(d.y)))
+            }));
+        }
+        self.cells = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_blinker_oscillates() {
+        let mut g = SparseGrid::from_cells([(0, 0), (1, 0), (2, 0)]);
+        g.update_states();
+        let mut cells: Vec<_> = g.live_cells().collect();
+        cells.sort_unstable();
+        assert_eq!(cells, vec![(1, -1), (1, 0), (1, 1)]);
+        g.update_states();
+        assert_eq!(g, SparseGrid::from_cells([(0, 0), (1, 0), (2, 0)]));
+    }
+
+    #[test]
+    fn test_glider_travels_forever() {
+        // .O.
+        // ..O
+        // OOO
+        let mut g = SparseGrid::from_cells([(1, 0), (2, 1), (0, 2), (1, 2), (2, 2)]);
+        for _ in 0..400 {
+            g.update_states();
+        }
+        assert_eq!(g.population(), 5);
+        assert!(g.is_alive(101, 100));
+        assert!(g.is_alive(102, 102));
+    }
+
+    #[test]
+    fn test_grid_round_trip() {
+        let grid = Grid::from_ascii("O..\n.OO\n...", 'O', '.');
+        let sparse = SparseGrid::from_grid(&grid, (-10, 20));
+        assert!(sparse.is_alive(-10, 20));
+        assert_eq!(sparse.population(), 3);
+        let window = sparse.to_grid((-10, 20), 3, 3);
+        assert_eq!(window.to_string(), "X  \n XX\n   \n");
+        assert_eq!(window.population, 3);
+    }
+
+    #[test]
+    fn test_set() {
+        let mut g = SparseGrid::new();
+        g.set(3, -4, true);
+        assert!(g.is_alive(3, -4));
+        g.set(3, -4, false);
+        assert_eq!(g.population(), 0);
+    }
+}
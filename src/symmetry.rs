@@ -0,0 +1,108 @@
+//! Random soups with symmetric starting patterns
+
+use crate::{CellState, Grid};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+/// `Symmetry` describes how a pattern made by `Grid::new_random_symmetric` is mirrored
+pub enum Symmetry {
+    /// The left and right halves mirror each other across the vertical centre line
+    Horizontal,
+    /// The top and bottom halves mirror each other across the horizontal centre line
+    Vertical,
+    /// Mirrored both left to right and top to bottom, so each quarter reflects the others
+    FourFold,
+    /// Unchanged when rotated half a turn about the centre of the grid
+    Point,
+}
+
+impl Symmetry {
+    /// The cells that `x`, `y` is mapped onto by this symmetry in a `width` by `height` grid,
+    /// including itself and possibly repeated
+    fn images(self, x: usize, y: usize, width: usize, height: usize) -> [(usize, usize); 4] {
+        let (mx, my) = (width - 1 - x, height - 1 - y);
+        match self {
+            Symmetry::Horizontal => [(x, y), (mx, y), (x, y), (x, y)],
+            Symmetry::Vertical => [(x, y), (x, my), (x, y), (x, y)],
+            Symmetry::FourFold => [(x, y), (mx, y), (x, my), (mx, my)],
+            Symmetry::Point => [(x, y), (mx, my), (x, y), (x, y)],
+        }
+    }
+}
+
+impl Grid<CellState> {
+    /// Generate a new `Grid` of a given `width` and `height` with a random pattern that has
+    /// the given `symmetry`. One fundamental region of the grid is filled at random, with each
+    /// cell alive with probability `0.5`, and reflected to fill the rest. The same `seed` always
+    /// produces the same grid.
+    /// ```
+    /// use gridlife::{Grid, Symmetry};
+    /// let grid = Grid::new_random_symmetric(8, 6, Symmetry::Horizontal, 42);
+    /// for line in grid.to_string().lines() {
+    ///     assert_eq!(line, line.chars().rev().collect::<String>());
+    /// }
+    /// ```
+    pub fn new_random_symmetric(
+        width: usize,
+        height: usize,
+        symmetry: Symmetry,
+        seed: u64,
+    ) -> Self {
+        let mut grid = Self::new_empty(width, height);
+        let mut rng = fastrand::Rng::with_seed(seed);
+        for y in 0..height {
+            for x in 0..width {
+                // Every cell copies the first of its images, which is filled in before it
+                let (sx, sy) = symmetry
+                    .images(x, y, width, height)
+                    .into_iter()
+                    .min_by_key(|&(ix, iy)| iy * width + ix)
+                    .unwrap_or((x, y));
+                let idx = y * width + x;
+                grid.cells[idx] = if (sx, sy) != (x, y) {
+                    grid.cells[sy * width + sx]
+                } else if rng.bool() {
+                    CellState::Alive(grid.alive_glyph)
+                } else {
+                    CellState::Dead(grid.dead_glyph)
+                };
+            }
+        }
+        grid.population = grid.calculate_population();
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_random_symmetric() {
+        for (width, height) in [(8, 6), (7, 5)] {
+            for symmetry in [
+                Symmetry::Horizontal,
+                Symmetry::Vertical,
+                Symmetry::FourFold,
+                Symmetry::Point,
+            ] {
+                let g = Grid::new_random_symmetric(width, height, symmetry, 7);
+                assert!(g.population > 0, "{symmetry:?} grid is empty");
+                for y in 0..height {
+                    for x in 0..width {
+                        for (ix, iy) in symmetry.images(x, y, width, height) {
+                            assert_eq!(g[(x, y)], g[(ix, iy)], "{symmetry:?} at ({x}, {y})");
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_new_random_symmetric_is_seeded() {
+        let a = Grid::new_random_symmetric(9, 9, Symmetry::FourFold, 3);
+        let b = Grid::new_random_symmetric(9, 9, Symmetry::FourFold, 3);
+        assert!(a.same_pattern(&b));
+    }
+}
@@ -0,0 +1,193 @@
+//! How the edges of a grid behave
+
+use crate::{CellState, Coord, Grid, Point};
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+#[non_exhaustive]
+/// `Topology` controls what lies beyond the edges of a `Grid` when counting neighbours
+pub enum Topology {
+    /// Cells beyond the edges of the grid do not exist, so edge cells have fewer neighbours
+    Bounded,
+    /// Cells beyond the edges of the grid are permanently alive, so edge cells always have live
+    /// neighbours. These cells are never counted in the population.
+    BoundedAlive,
+    /// The grid wraps around both horizontally and vertically, so cells on opposite edges are
+    /// neighbours
+    Toroidal,
+    /// The grid wraps around only along the axes that are enabled, like a tube, and is bounded
+    /// along the others. With both enabled this is the same as `Toroidal`.
+    Cylinder {
+        /// Whether the left and right edges are neighbours
+        wrap_x: bool,
+        /// Whether the top and bottom edges are neighbours
+        wrap_y: bool,
+    },
+}
+
+impl Default for Topology {
+    fn default() -> Self {
+        Topology::Bounded
+    }
+}
+
+impl Topology {
+    /// Maps `p` onto a cell within a `width` by `height` grid, or `None` if there is no such
+    /// cell under this topology
+    pub(crate) fn resolve(&self, p: Point, width: usize, height: usize) -> Option<Point> {
+        let (wrap_x, wrap_y) = match *self {
+            Topology::Bounded | Topology::BoundedAlive => (false, false),
+            Topology::Toroidal => (true, true),
+            Topology::Cylinder { wrap_x, wrap_y } => (wrap_x, wrap_y),
+        };
+        let axis = |v: Coord, len: usize, wrap: bool| {
+            if wrap && len > 0 {
+                Some(v.rem_euclid(len as Coord))
+            } else {
+                (v >= 0 && (v as usize) < len).then(|| v)
+            }
+        };
+        Some(Point::new(
+            axis(p.x, width, wrap_x)?,
+            axis(p.y, height, wrap_y)?,
+        ))
+    }
+
+    /// Whether positions that `resolve` to no cell should be counted as live neighbours
+    pub(crate) fn outside_is_alive(&self) -> bool {
+        matches!(self, Topology::BoundedAlive)
+    }
+}
+
+impl Grid<CellState> {
+    /// The state of every cell beyond a bounded edge, see `background`
+    pub(crate) fn outside_cell(&self) -> CellState {
+        if self.topology.outside_is_alive() {
+            CellState::Alive(self.alive_glyph)
+        } else {
+            self.background
+        }
+    }
+
+    /// Returns the cell at `point` after wrapping it according to the `topology`, or the
+    /// `background` if it lies beyond a bounded edge. Unlike `Grid::get` this gives a state
+    /// for every point, matching what is counted as a neighbour.
+    /// ```
+    /// use gridlife::{CellState, Grid, Point, Topology};
+    /// let mut grid = Grid::from_ascii("O.", 'O', '.');
+    /// assert_eq!(grid.cell_or_background(Point::new(-1, 0)), CellState::Dead('.'));
+    /// grid.background = CellState::Alive('O');
+    /// assert_eq!(grid.cell_or_background(Point::new(5, 5)), CellState::Alive('O'));
+    /// grid.topology = Topology::Toroidal;
+    /// assert_eq!(grid.cell_or_background(Point::new(-1, 0)), CellState::Dead('.'));
+    /// assert_eq!(grid.cell_or_background(Point::new(2, 0)), CellState::Alive('O'));
+    /// ```
+    pub fn cell_or_background(&self, point: Point) -> CellState {
+        match self.topology.resolve(point, self.width, self.height) {
+            Some(p) => self[p],
+            None => self.outside_cell(),
+        }
+    }
+
+    /// Returns a copy of the grid surrounded by a one cell border of "ghost" cells, showing the
+    /// cells that lie just beyond each edge under the grid's `topology`. On a toroidal grid the
+    /// border repeats the opposite edges, so a glider about to wrap around can be seen before it
+    /// re-enters. Where an edge does not wrap the border shows the `background`.
+    ///
+    /// This is only meant for display, the returned grid is `width + 2` by `height + 2` and
+    /// uses the default rules and topology.
+    /// ```
+    /// use gridlife::{Grid, Topology};
+    /// let mut grid = Grid::from_ascii("O..\n...\n..O", 'O', '.');
+    /// grid.topology = Topology::Toroidal;
+    /// assert_eq!(grid.with_ghost_border().to_string(), "O..O.\n.O..O\n.....\nO..O.\n.O..O\n");
+    /// ```
+    pub fn with_ghost_border(&self) -> Grid<CellState> {
+        let (width, height) = (self.width + 2, self.height + 2);
+        let mut ghost = Self::empty_with_glyphs(width, height, self.alive_glyph, self.dead_glyph);
+        ghost.dying_glyph = self.dying_glyph;
+        for y in 0..height {
+            for x in 0..width {
+                let p = Point::new(x as Coord - 1, y as Coord - 1);
+                ghost.cells[y * width + x] = self.cell_or_background(p);
+            }
+        }
+        ghost.population = ghost.calculate_population();
+        ghost
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Grid;
+
+    #[test]
+    fn test_bounded_resolve() {
+        let t = Topology::Bounded;
+        assert_eq!(t.resolve(Point::new(1, 2), 3, 3), Some(Point::new(1, 2)));
+        assert_eq!(t.resolve(Point::new(-1, 2), 3, 3), None);
+        assert_eq!(t.resolve(Point::new(1, 3), 3, 3), None);
+    }
+
+    #[test]
+    fn test_toroidal_resolve() {
+        let t = Topology::Toroidal;
+        assert_eq!(t.resolve(Point::new(-1, 3), 3, 3), Some(Point::new(2, 0)));
+        assert_eq!(t.resolve(Point::new(4, -4), 3, 3), Some(Point::new(1, 2)));
+        assert_eq!(t.resolve(Point::new(0, 0), 0, 0), None);
+    }
+
+    #[test]
+    fn test_cylinder_resolve() {
+        let t = Topology::Cylinder {
+            wrap_x: true,
+            wrap_y: false,
+        };
+        assert_eq!(t.resolve(Point::new(3, 1), 3, 3), Some(Point::new(0, 1)));
+        assert_eq!(t.resolve(Point::new(-1, 2), 3, 3), Some(Point::new(2, 2)));
+        assert_eq!(t.resolve(Point::new(1, -1), 3, 3), None);
+        let t = Topology::Cylinder {
+            wrap_x: false,
+            wrap_y: true,
+        };
+        assert_eq!(t.resolve(Point::new(1, -1), 3, 3), Some(Point::new(1, 2)));
+        assert_eq!(t.resolve(Point::new(3, 1), 3, 3), None);
+    }
+
+    #[test]
+    fn test_with_ghost_border() {
+        let mut g = Grid::from_ascii("O.\n.O\n..", 'O', '.');
+        assert_eq!(
+            g.with_ghost_border().to_string(),
+            "....\n.O..\n..O.\n....\n....\n"
+        );
+        g.topology = Topology::Cylinder {
+            wrap_x: true,
+            wrap_y: false,
+        };
+        assert_eq!(
+            g.with_ghost_border().to_string(),
+            "....\n.O.O\nO.O.\n....\n....\n"
+        );
+        assert_eq!(Grid::new_empty(0, 0).with_ghost_border().cells.len(), 4);
+    }
+
+    #[test]
+    fn test_cylinder_horizontal_wrap() {
+        let tube = Topology::Cylinder {
+            wrap_x: true,
+            wrap_y: false,
+        };
+        // A blinker on the right edge re-enters on the left
+        let mut g = Grid::from_ascii(".....\n....O\n....O\n....O\n.....", 'O', '.');
+        g.topology = tube;
+        g.update_states();
+        assert_eq!(g.to_string(), ".....\n.....\nO..OO\n.....\n.....\n");
+
+        // A blinker on the top edge loses the cell that would be above it
+        let mut g = Grid::from_ascii(".OOO.\n.....\n.....\n.....\n.....", 'O', '.');
+        g.topology = tube;
+        g.update_states();
+        assert_eq!(g.to_string(), "..O..\n..O..\n.....\n.....\n.....\n");
+    }
+}
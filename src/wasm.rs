@@ -0,0 +1,99 @@
+//! Bindings for driving a grid from JavaScript with `wasm-bindgen`
+
+use alloc::vec::Vec;
+
+use wasm_bindgen::prelude::*;
+
+use crate::{CellState, Grid};
+
+#[wasm_bindgen]
+#[derive(Debug, Clone)]
+/// `WasmGrid` wraps a `Grid` for use from JavaScript.
+///
+/// Alongside the grid it keeps one byte per cell in row-major order, `1` for alive and `0`
+/// otherwise, which JavaScript can read straight out of the WebAssembly memory using
+/// `cells_ptr`, `width` and `height`:
+/// ```js
+/// const grid = new WasmGrid(64, 64);
+/// grid.randomize(0.5);
+/// grid.step();
+/// const cells = new Uint8Array(memory.buffer, grid.cells_ptr(), grid.width() * grid.height());
+/// ```
+/// The pointer is only valid until the next call that changes the grid.
+pub struct WasmGrid {
+    grid: Grid<CellState>,
+    bytes: Vec<u8>,
+}
+
+#[wasm_bindgen]
+impl WasmGrid {
+    /// Create a new `WasmGrid` of a given `width` and `height` where every cell is dead
+    #[wasm_bindgen(constructor)]
+    pub fn new(width: usize, height: usize) -> WasmGrid {
+        let mut grid = WasmGrid {
+            grid: Grid::new_empty(width, height),
+            bytes: Vec::new(),
+        };
+        grid.refresh();
+        grid
+    }
+
+    /// Re-fills the grid with live cells, each alive with probability `density`
+    pub fn randomize(&mut self, density: f64) {
+        self.grid.randomize(density);
+        self.refresh();
+    }
+
+    /// Advance one generation, returning whether any cell changed state
+    pub fn step(&mut self) -> bool {
+        let changed = self.grid.step();
+        self.refresh();
+        changed
+    }
+
+    /// Number of cells in each row
+    pub fn width(&self) -> usize {
+        self.grid.width
+    }
+
+    /// Number of rows
+    pub fn height(&self) -> usize {
+        self.grid.height
+    }
+
+    /// Number of live cells
+    pub fn population(&self) -> usize {
+        self.grid.population
+    }
+
+    /// Pointer to `width * height` bytes holding the state of each cell, `1` for alive and `0`
+    /// otherwise
+    pub fn cells_ptr(&self) -> *const u8 {
+        self.bytes.as_ptr()
+    }
+
+    fn refresh(&mut self) {
+        self.bytes = self.grid.as_bytes();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wasm_grid() {
+        let mut grid = WasmGrid::new(3, 2);
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid.bytes, [0; 6]);
+
+        grid.randomize(1.0);
+        assert_eq!(grid.bytes, [1; 6]);
+        assert_eq!(grid.population(), 6);
+
+        assert!(grid.step());
+        // Only the corners have few enough neighbours to survive
+        assert_eq!(grid.bytes, [1, 0, 1, 1, 0, 1]);
+        assert_eq!(grid.cells_ptr(), grid.bytes.as_ptr());
+    }
+}
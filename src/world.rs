@@ -0,0 +1,120 @@
+//! Running several independent grids side by side
+
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt::{self, Display};
+
+use crate::{CellState, Grid};
+
+#[derive(Debug, Clone)]
+/// `World` holds several independent grids, each with its own rules and glyphs, and displays
+/// them tiled `columns` grids to a row.
+///
+/// Grids are separated by a column of `|` and each row of grids by a row of `-`, so grids of
+/// different sizes can be compared side by side.
+/// ```
+/// use gridlife::{Grid, World};
+/// let mut world = World::new(2);
+/// world.push(Grid::from_ascii("O.\n.O", 'O', '.'));
+/// world.push(Grid::from_ascii("##\n##", '#', ' '));
+/// world.step_all();
+/// assert_eq!(world.to_string(), "..|##\n..|##\n");
+/// ```
+pub struct World {
+    /// The grids in the world, laid out left to right then top to bottom
+    pub grids: Vec<Grid<CellState>>,
+    /// Number of grids displayed on each row
+    pub columns: usize,
+}
+
+impl World {
+    /// Create a new empty `World` displaying `columns` grids on each row.
+    /// A `columns` of `0` is treated as `1`.
+    pub fn new(columns: usize) -> Self {
+        World {
+            grids: Vec::new(),
+            columns: columns.max(1),
+        }
+    }
+
+    /// Adds `grid` after the existing grids
+    pub fn push(&mut self, grid: Grid<CellState>) {
+        self.grids.push(grid);
+    }
+
+    /// Advances every grid by one generation using its own rules
+    pub fn step_all(&mut self) {
+        for grid in &mut self.grids {
+            grid.update_states();
+        }
+    }
+}
+
+impl Display for World {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let rows = self.grids.chunks(self.columns.max(1));
+        let last = rows.len().saturating_sub(1);
+        for (row, grids) in rows.enumerate() {
+            let widths: Vec<usize> = grids.iter().map(|g| g.width * g.cell_width()).collect();
+            let rendered: Vec<String> = grids.iter().map(ToString::to_string).collect();
+            let mut lines: Vec<_> = rendered.iter().map(|s| s.lines()).collect();
+            let height = grids.iter().map(|g| g.height).max().unwrap_or(0);
+            for _ in 0..height {
+                for (col, (line, &width)) in lines.iter_mut().zip(&widths).enumerate() {
+                    if col > 0 {
+                        write!(f, "|")?;
+                    }
+                    match line.next() {
+                        Some(line) => write!(f, "{line}")?,
+                        // Shorter grids are padded below with blank lines
+                        None => write!(f, "{}", " ".repeat(width))?,
+                    }
+                }
+                writeln!(f)?;
+            }
+            if row < last {
+                let separator: Vec<String> = widths.iter().map(|&w| "-".repeat(w)).collect();
+                writeln!(f, "{}", separator.join("+"))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Ruleset;
+
+    #[test]
+    fn test_world_display() {
+        let mut world = World::new(2);
+        world.push(Grid::from_ascii("O..\n.O.", 'O', '.'));
+        world.push(Grid::from_ascii("#\n.\n#", '#', '.'));
+        world.push(Grid::from_ascii("OO", 'O', '.'));
+        assert_eq!(world.to_string(), "O..|#\n.O.|.\n   |#\n---+-\nOO\n");
+
+        let mut world = World::new(2);
+        world.push(Grid::from_ascii("🦠", '🦠', ' '));
+        world.push(Grid::from_ascii("O\nO", 'O', '.'));
+        assert_eq!(world.to_string(), "🦠|O\n  |O\n");
+    }
+
+    #[test]
+    fn test_world_step_all_keeps_rules() {
+        let mut highlife = Grid::from_ascii(".....\n.OOO.\n.....", 'O', '.');
+        highlife.rules = Ruleset::new(&[3, 6], &[2, 3]);
+        let mut world = World::new(1);
+        world.push(Grid::from_ascii(".....\n.OOO.\n.....", 'O', '.'));
+        world.push(highlife);
+        world.push(Grid::from_ascii("OO\nOO", '#', '.'));
+        world.step_all();
+        assert_eq!(world.grids[0].population, 3);
+        assert_eq!(world.grids[1].rules, Ruleset::new(&[3, 6], &[2, 3]));
+        assert_eq!(world.grids[2].population, 0);
+        assert_eq!(World::new(0).columns, 1);
+        assert_eq!(World::new(3).to_string(), "");
+    }
+}